@@ -0,0 +1,150 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io::ErrorKind, path::Path};
+
+use crate::{error::Error, Answer, Case, Part};
+
+/// Known answers per day, part and case, read from `answers.txt` in the input directory
+///
+/// Each line is `<day> <part> <case> <answer>`, where the answer is the rest of the line with
+/// `\n` separating the rows of a grid answer. Empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Default)]
+pub struct Answers {
+    expected: BTreeMap<(u32, Part, String), String>,
+}
+
+/// Outcome of comparing an answer with the manifest
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail(String),
+    Unknown,
+    Unsolved,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail(expected) => write!(f, "FAIL (expected {expected})"),
+            Status::Unknown => write!(f, "UNKNOWN"),
+            Status::Unsolved => write!(f, "UNSOLVED"),
+        }
+    }
+}
+
+impl Answers {
+    pub const FILE_NAME: &'static str = "answers.txt";
+
+    /// Reads the manifest in `dir`, a missing file means no answers are known
+    pub fn load(dir: &Path) -> Result<Self, Error> {
+        let path = dir.join(Self::FILE_NAME);
+        let source = path.display().to_string();
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|e| e.with_source(Some(&source))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::from(e).with_source(Some(&source))),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let mut expected = BTreeMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, char::is_whitespace);
+            let (Some(day), Some(part), Some(case), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                return Err(Error::at_line(i, "expected '<day> <part> <case> <answer>'"));
+            };
+
+            let day = day
+                .parse::<u32>()
+                .map_err(|_| Error::at_line(i, format!("invalid day '{day}'")))?;
+            let part = part.parse::<Part>().map_err(|e| Error::at_line(i, e))?;
+            let case = case.parse::<Case>().map_err(|e| Error::at_line(i, e))?;
+            let answer = answer.trim().replace("\\n", "\n");
+            expected.insert((day, part, case.to_string()), answer);
+        }
+        Ok(Self { expected })
+    }
+
+    pub fn expected(&self, day: u32, part: Part, case: &Case) -> Option<&str> {
+        self.expected
+            .get(&(day, part, case.to_string()))
+            .map(|x| x.as_str())
+    }
+
+    pub fn check(&self, day: u32, part: Part, case: &Case, answer: &Answer) -> Status {
+        match self.expected(day, part, case) {
+            _ if *answer == Answer::Unsolved => Status::Unsolved,
+            Some(expected) if expected == answer.to_string() => Status::Pass,
+            Some(expected) => Status::Fail(expected.to_string()),
+            None => Status::Unknown,
+        }
+    }
+
+    /// Every known answer, ordered by day, part and case
+    #[allow(dead_code)]
+    pub fn iter(&self) -> impl Iterator<Item = (u32, Part, Case, &str)> {
+        self.expected.iter().map(|((day, part, case), answer)| {
+            let case = case.parse().expect("cases are stored in parsable form");
+            (*day, *part, case, answer.as_str())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_check() {
+        let answers = Answers::parse(
+            "# day part case answer\n\
+             1 1 real 11\n\
+             \n\
+             17 1 example:2 0,1,2\n",
+        )
+        .unwrap();
+
+        assert_eq!(answers.expected(1, Part::One, &Case::Real), Some("11"));
+        assert_eq!(
+            answers.check(1, Part::One, &Case::Real, &Answer::from(11)),
+            Status::Pass
+        );
+        assert_eq!(
+            answers.check(1, Part::One, &Case::Real, &Answer::from(12)),
+            Status::Fail("11".to_string())
+        );
+        assert_eq!(
+            answers.check(1, Part::Two, &Case::Real, &Answer::from(11)),
+            Status::Unknown
+        );
+        let case = Case::Example("2".to_string());
+        assert_eq!(answers.expected(17, Part::One, &case), Some("0,1,2"));
+        assert_eq!(
+            answers.check(1, Part::One, &Case::Real, &Answer::Unsolved),
+            Status::Unsolved
+        );
+        assert_eq!(answers.iter().count(), 2);
+    }
+
+    #[test]
+    fn grid_answer() {
+        let answers = Answers::parse("10 2 real #.\\n.#\n").unwrap();
+        assert_eq!(
+            answers.check(10, Part::Two, &Case::Real, &Answer::grid(["#.", ".#"])),
+            Status::Pass
+        );
+    }
+
+    #[test]
+    fn parse_error_has_line() {
+        let e = Answers::parse("1 1 real 11\n1 3 real 5\n").unwrap_err();
+        assert_eq!(e.line, Some(1));
+        assert!(Answers::parse("1 1 real\n").is_err());
+    }
+}
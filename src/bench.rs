@@ -0,0 +1,319 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs,
+    hint::black_box,
+    io::ErrorKind,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{cache, error::Error, pool, registry::DayEntry, Case, Part, ProblemData};
+
+/// Summary of repeated timings of the same step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// `samples` must not be empty
+    pub fn new(samples: &[Duration]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let mid = sorted.len() / 2;
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[mid - 1] + sorted[mid]) / 2
+        } else {
+            sorted[mid]
+        };
+        Self {
+            min: sorted[0],
+            median,
+            mean: sorted.iter().sum::<Duration>() / sorted.len() as u32,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "min {:>10}  median {:>10}  mean {:>10}",
+            format!("{:.2?}", self.min),
+            format!("{:.2?}", self.median),
+            format!("{:.2?}", self.mean)
+        )
+    }
+}
+
+/// Timings of parsing the input and of every benchmarked part of a day
+#[derive(Debug)]
+pub struct Timings {
+    pub parse: Stats,
+    pub parts: Vec<(Part, Stats)>,
+}
+
+impl Timings {
+    /// Every timed step with its name, `parse`, `part1` or `part2`
+    pub fn steps(&self) -> impl Iterator<Item = (String, Stats)> + '_ {
+        std::iter::once(("parse".to_string(), self.parse)).chain(
+            self.parts
+                .iter()
+                .map(|(part, stats)| (format!("part{part}"), *stats)),
+        )
+    }
+}
+
+/// How a step's median changed compared to a saved run
+#[derive(Debug, PartialEq)]
+pub enum Change {
+    Slower(f64),
+    Faster(f64),
+    Same(f64),
+}
+
+impl Change {
+    /// Compares medians, changes within `threshold` percent count as the same
+    pub fn new(baseline: &Stats, current: &Stats, threshold: f64) -> Self {
+        let old = baseline.median.as_secs_f64();
+        let new = current.median.as_secs_f64();
+        let percent = if old == 0.0 {
+            0.0
+        } else {
+            (new - old) / old * 100.0
+        };
+        if percent > threshold {
+            Change::Slower(percent)
+        } else if percent < -threshold {
+            Change::Faster(percent)
+        } else {
+            Change::Same(percent)
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Change::Slower(p) => write!(f, "{p:+.1}% SLOWER"),
+            Change::Faster(p) => write!(f, "{p:+.1}% faster"),
+            Change::Same(p) => write!(f, "{p:+.1}%"),
+        }
+    }
+}
+
+/// Saved benchmark results, keyed by a label, the day, the input case and the step name
+///
+/// Stored one result per line as `<label> <day> <case> <step> <min> <median> <mean>`, with the
+/// times in nanoseconds and the case escaped like in the answer cache.
+#[derive(Debug, Default)]
+pub struct History {
+    results: BTreeMap<(String, u32, String, String), Stats>,
+}
+
+impl History {
+    pub const FILE_NAME: &'static str = "bench_history.txt";
+
+    /// Reads the history at `path`, a missing file means nothing is saved yet
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let source = path.display().to_string();
+        match fs::read_to_string(path) {
+            Ok(content) => Self::parse(&content).map_err(|e| e.with_source(Some(&source))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::from(e).with_source(Some(&source))),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let mut results = BTreeMap::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [label, day, case, step, min, median, mean] = fields[..] else {
+                return Err(Error::at_line(
+                    i,
+                    "expected '<label> <day> <case> <step> <min> <median> <mean>'",
+                ));
+            };
+            let day = day
+                .parse::<u32>()
+                .map_err(|_| Error::at_line(i, format!("invalid day '{day}'")))?;
+            let case = cache::unescape(case)
+                .and_then(|x| x.parse::<Case>())
+                .map_err(|e| Error::at_line(i, e))?;
+            let nanos = |x: &str| {
+                x.parse::<u64>()
+                    .map(Duration::from_nanos)
+                    .map_err(|_| Error::at_line(i, format!("invalid time '{x}'")))
+            };
+            let stats = Stats {
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+            };
+            let key = (label.to_string(), day, case.to_string(), step.to_string());
+            results.insert(key, stats);
+        }
+        Ok(Self { results })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = self
+            .results
+            .iter()
+            .map(|((label, day, case, step), s)| {
+                format!(
+                    "{label} {day} {} {step} {} {} {}\n",
+                    cache::escape(case),
+                    s.min.as_nanos(),
+                    s.median.as_nanos(),
+                    s.mean.as_nanos()
+                )
+            })
+            .collect::<String>();
+        fs::write(path, content)
+            .map_err(|e| Error::from(e).with_source(Some(&path.display().to_string())))
+    }
+
+    /// Stores the timings of a day under `label`, replacing older results with the same label
+    pub fn record(&mut self, label: &str, day: u32, case: &Case, timings: &Timings) {
+        for (step, stats) in timings.steps() {
+            let key = (label.to_string(), day, case.to_string(), step);
+            self.results.insert(key, stats);
+        }
+    }
+
+    pub fn get(&self, label: &str, day: u32, case: &Case, step: &str) -> Option<&Stats> {
+        self.results
+            .get(&(label.to_string(), day, case.to_string(), step.to_string()))
+    }
+
+    /// Whether anything was saved under `label`
+    pub fn has_label(&self, label: &str) -> bool {
+        self.results.keys().any(|(l, ..)| l == label)
+    }
+}
+
+/// Creates a fresh solver `runs` times, timing the parsing and each of `parts` separately.
+/// Reading the input is not included, every run parses a copy of `data`. A panic while parsing or
+/// solving is caught and ends the benchmark of the day with an error
+pub fn bench_day(
+    entry: &DayEntry,
+    data: &ProblemData,
+    parts: &[Part],
+    runs: usize,
+) -> Result<Timings, Error> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut solve = vec![Vec::with_capacity(runs); parts.len()];
+
+    for _ in 0..runs {
+        let (data, new) = (data.clone(), entry.new);
+        let (_, (solver, time)) = pool::run_isolated((), None, move |_| {
+            let start = Instant::now();
+            let solver = new(data);
+            (solver, start.elapsed())
+        })
+        .map_err(|failure| Error::new(format!("parsing: {failure}")))?;
+        parse.push(time);
+
+        let mut solver = solver?;
+        for (i, &part) in parts.iter().enumerate() {
+            let (s, time) = pool::run_isolated(solver, None, move |s| {
+                let start = Instant::now();
+                black_box(s.solve(part));
+                start.elapsed()
+            })
+            .map_err(|failure| Error::new(format!("part {part}: {failure}")))?;
+            solver = s;
+            solve[i].push(time);
+        }
+    }
+
+    Ok(Timings {
+        parse: Stats::new(&parse),
+        parts: parts
+            .iter()
+            .zip(solve)
+            .map(|(&part, samples)| (part, Stats::new(&samples)))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn stats_odd() {
+        let s = Stats::new(&[ms(5), ms(1), ms(3)]);
+        assert_eq!(s.min, ms(1));
+        assert_eq!(s.median, ms(3));
+        assert_eq!(s.mean, ms(3));
+    }
+
+    #[test]
+    fn stats_even() {
+        let s = Stats::new(&[ms(4), ms(1), ms(2), ms(9)]);
+        assert_eq!(s.min, ms(1));
+        assert_eq!(s.median, ms(3));
+        assert_eq!(s.mean, ms(4));
+    }
+
+    #[test]
+    fn change_threshold() {
+        let stats = |x| Stats {
+            min: ms(x),
+            median: ms(x),
+            mean: ms(x),
+        };
+        let change = |old, new| Change::new(&stats(old), &stats(new), 10.0);
+        assert!(matches!(change(100, 125), Change::Slower(p) if (p - 25.0).abs() < 1e-9));
+        assert!(matches!(change(100, 50), Change::Faster(p) if (p + 50.0).abs() < 1e-9));
+        assert!(matches!(change(100, 105), Change::Same(_)));
+        assert_eq!(change(100, 125).to_string(), "+25.0% SLOWER");
+    }
+
+    #[test]
+    fn history_round_trip() {
+        let timings = Timings {
+            parse: Stats::new(&[ms(1)]),
+            parts: vec![(Part::Two, Stats::new(&[ms(2), ms(4)]))],
+        };
+        let mut history = History::default();
+        let case = Case::Example("two words".to_string());
+        history.record("base", 7, &case, &timings);
+
+        let path = std::env::temp_dir().join(format!("aoc_history_{}.txt", std::process::id()));
+        history.save(&path).unwrap();
+        let loaded = History::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.get("base", 7, &case, "parse"), Some(&timings.parse));
+        assert_eq!(
+            loaded.get("base", 7, &case, "part2").map(|x| x.median),
+            Some(ms(3))
+        );
+        assert_eq!(loaded.get("base", 7, &case, "part1"), None);
+        assert_eq!(loaded.get("base", 7, &Case::Real, "parse"), None);
+        assert!(loaded.has_label("base") && !loaded.has_label("other"));
+        assert!(History::parse("base 7 real parse 1 2\n").is_err());
+    }
+
+    #[test]
+    fn bench_test_input() {
+        let entry = crate::registry::get(3).unwrap();
+        let data = ProblemData::from_file(3, &crate::Case::Test).unwrap();
+        let t = bench_day(entry, &data, &[Part::One, Part::Two], 3).unwrap();
+        assert_eq!(t.parts.len(), 2);
+        assert_eq!(t.parts[1].0, Part::Two);
+    }
+}
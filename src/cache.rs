@@ -0,0 +1,203 @@
+use std::{collections::BTreeMap, fs, io::ErrorKind, path::Path};
+
+use crate::{error::Error, Answer, Case, Part};
+
+/// 64 bit FNV-1a hash of an input, used to notice when it changed
+pub fn checksum(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Answers computed by earlier runs, each stored with the checksum of the input it was solved
+/// from, kept in `.answer_cache.txt` in the input directory
+///
+/// Each line is `<day> <part> <case> <checksum> <kind> <answer>`, with the checksum in hex. In the
+/// case and answer a backslash, newline and space are written as `\\`, `\n` and `\s`. An answer
+/// is only reused while the checksum of the input still matches, changes to the solvers
+/// themselves are not noticed.
+#[derive(Debug, Default)]
+pub struct Cache {
+    answers: BTreeMap<(u32, Part, String), (u64, Answer)>,
+    changed: bool,
+}
+
+/// Makes `s` safe to store as one field of a line
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            ' ' => out.push_str("\\s"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+pub fn unescape(s: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('\\') => out.push('\\'),
+            Some('n') => out.push('\n'),
+            Some('s') => out.push(' '),
+            Some(x) => return Err(format!("invalid escape '\\{x}'")),
+            None => return Err("unfinished escape at the end".to_string()),
+        }
+    }
+    Ok(out)
+}
+
+impl Cache {
+    pub const FILE_NAME: &'static str = ".answer_cache.txt";
+
+    /// Reads the cache in `dir`, a missing file means nothing is cached yet
+    pub fn load(dir: &Path) -> Result<Self, Error> {
+        let path = dir.join(Self::FILE_NAME);
+        let source = path.display().to_string();
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content).map_err(|e| e.with_source(Some(&source))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(Error::from(e).with_source(Some(&source))),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, Error> {
+        let mut answers = BTreeMap::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields = line.splitn(6, ' ').collect::<Vec<_>>();
+            let [day, part, case, checksum, kind, answer] = fields[..] else {
+                return Err(Error::at_line(
+                    i,
+                    "expected '<day> <part> <case> <checksum> <kind> <answer>'",
+                ));
+            };
+
+            let day = day
+                .parse::<u32>()
+                .map_err(|_| Error::at_line(i, format!("invalid day '{day}'")))?;
+            let part = part.parse::<Part>().map_err(|e| Error::at_line(i, e))?;
+            let case = unescape(case)
+                .and_then(|x| x.parse::<Case>())
+                .map_err(|e| Error::at_line(i, e))?;
+            let checksum = u64::from_str_radix(checksum, 16)
+                .map_err(|_| Error::at_line(i, format!("invalid checksum '{checksum}'")))?;
+            let answer = unescape(answer).map_err(|e| Error::at_line(i, e))?;
+            let answer = match kind {
+                "int" => answer
+                    .parse()
+                    .map(Answer::Int)
+                    .map_err(|_| Error::at_line(i, format!("invalid number '{answer}'")))?,
+                "text" => Answer::Text(answer),
+                "grid" => Answer::Grid(answer),
+                x => return Err(Error::at_line(i, format!("invalid kind '{x}'"))),
+            };
+            answers.insert((day, part, case.to_string()), (checksum, answer));
+        }
+        Ok(Self {
+            answers,
+            ..Self::default()
+        })
+    }
+
+    /// Writes the cache to `dir` if anything was added since it was loaded
+    pub fn save(&self, dir: &Path) -> Result<(), Error> {
+        if !self.changed {
+            return Ok(());
+        }
+        let mut content = String::new();
+        for ((day, part, case), (checksum, answer)) in &self.answers {
+            let (kind, answer) = match answer {
+                Answer::Int(x) => ("int", x.to_string()),
+                Answer::Text(x) => ("text", escape(x)),
+                Answer::Grid(x) => ("grid", escape(x)),
+                Answer::Unsolved => continue,
+            };
+            let case = escape(case);
+            content += &format!("{day} {part} {case} {checksum:016x} {kind} {answer}\n");
+        }
+        let path = dir.join(Self::FILE_NAME);
+        fs::write(&path, content)
+            .map_err(|e| Error::from(e).with_source(Some(&path.display().to_string())))
+    }
+
+    /// The stored answer, if it was solved from the same input
+    pub fn get(&self, day: u32, part: Part, case: &Case, checksum: u64) -> Option<&Answer> {
+        match self.answers.get(&(day, part, case.to_string())) {
+            Some((c, answer)) if *c == checksum => Some(answer),
+            _ => None,
+        }
+    }
+
+    /// Stores an answer, replacing the one solved from an older input. Unsolved parts are not
+    /// stored, so they are tried again once solved
+    pub fn insert(&mut self, day: u32, part: Part, case: &Case, checksum: u64, answer: &Answer) {
+        if matches!(answer, Answer::Unsolved) {
+            return;
+        }
+        let entry = (checksum, answer.clone());
+        let old = self.answers.insert((day, part, case.to_string()), entry);
+        self.changed |= old.is_none_or(|(c, a)| c != checksum || a != *answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a() {
+        assert_eq!(checksum(""), 0xcbf29ce484222325);
+        assert_eq!(checksum("a"), 0xaf63dc4c8601ec8c);
+        assert_ne!(checksum("1 2\n"), checksum("1 3\n"));
+    }
+
+    #[test]
+    fn changed_input_is_not_reused() {
+        let mut cache = Cache::default();
+        let answer = Answer::from(42);
+        cache.insert(7, Part::One, &Case::Real, 1, &answer);
+        assert_eq!(cache.get(7, Part::One, &Case::Real, 1), Some(&answer));
+        assert_eq!(cache.get(7, Part::One, &Case::Real, 2), None);
+        assert_eq!(cache.get(7, Part::One, &Case::Test, 1), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let dir = std::env::temp_dir().join(format!("aoc_cache_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut cache = Cache::default();
+        let case = Case::Example("large one".to_string());
+        let text = Answer::from("a b\\n\nc");
+        cache.insert(17, Part::One, &case, 0xabc, &Answer::from("0,1,2"));
+        cache.insert(17, Part::Two, &case, 0xabc, &text);
+        cache.insert(10, Part::Two, &Case::Real, 5, &Answer::grid(["#.", ".#"]));
+        cache.insert(1, Part::Two, &Case::Real, 5, &Answer::Unsolved);
+        cache.save(&dir).unwrap();
+
+        let loaded = Cache::load(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded.get(17, Part::Two, &case, 0xabc), Some(&text));
+        assert_eq!(
+            loaded.get(17, Part::One, &case, 0xabc),
+            Some(&Answer::from("0,1,2"))
+        );
+        assert_eq!(
+            loaded.get(10, Part::Two, &Case::Real, 5),
+            Some(&Answer::grid(["#.", ".#"]))
+        );
+        assert_eq!(loaded.get(1, Part::Two, &Case::Real, 5), None);
+        assert!(Cache::parse("1 1 real zz int 5\n").is_err());
+        assert!(Cache::parse("1 1 real 5 text a\\x\n").is_err());
+    }
+}
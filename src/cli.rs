@@ -0,0 +1,586 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{Case, Input, Part};
+
+pub const USAGE: &str = "\
+usage: aoc_2024 [-v...] <command> [options]
+
+    -v, --verbose   print debug output of the solvers to stderr, repeat for more detail
+
+commands:
+    run     solve a day
+    bench   time parsing and solving of a day
+    report  solve every day and summarize the answers in a table
+    list    list implemented days
+    new-day <N> [--title <TITLE>] [--input-dir <DIR>]
+            generate the module, registry entry and input files of a new day, the
+            inputs go to DIR, $AOC_INPUT_DIR or the data directory of the checkout
+
+run options:
+    --day <N>       day to solve
+    --all           solve every implemented day
+    --part <1|2>    only solve one part (default: both)
+    --case <CASE>   input to use: real, test or example:<name> (default: real)
+    --examples      solve every example of the day(s)
+    --input-dir <DIR>
+                    directory with the dayN.txt inputs (default: $AOC_INPUT_DIR or data)
+    --input <FILE>  read the input of a single day from FILE, or from stdin if FILE is -
+    --cached        reuse answers of earlier runs if the input didn't change
+    --jobs <N>      solve up to N days at the same time (default: 1)
+    --timeout <SECONDS>
+                    give up on a part after this long (default: no limit)
+
+bench options:
+    all run options except --examples, --jobs, --cached and --timeout, and
+    --runs <N>      number of times to parse and solve each day (default: 10)
+    --save <LABEL>  save the results under LABEL
+    --compare <LABEL>
+                    compare medians with the results saved under LABEL
+    --threshold <PERCENT>
+                    how much slower a step may get before it is flagged (default: 10)
+    --history <FILE>
+                    where results are saved (default: bench_history.txt)
+
+report options:
+    --part, --case, --input-dir and --timeout like run, and
+    --format <FORMAT>
+                    table, json or csv (default: table)";
+
+/// A parsed command line
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(BenchArgs),
+    Report(ReportArgs),
+    List,
+    NewDay(NewDayArgs),
+    Help,
+}
+
+/// Which days a command applies to
+#[derive(Debug, PartialEq, Eq)]
+pub enum Days {
+    One(u32),
+    All,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct RunArgs {
+    pub days: Days,
+    pub part: Option<Part>,
+    pub case: Case,
+    /// Run every example instead of `case`
+    pub examples: bool,
+    /// `None` when the default input directory should be used
+    pub input: Option<Input>,
+    /// Reuse answers cached by earlier runs on the same input
+    pub cached: bool,
+    /// Number of days solved at the same time
+    pub jobs: usize,
+    /// How long a part may take, `None` for no limit
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BenchArgs {
+    pub run: RunArgs,
+    pub runs: usize,
+    pub save: Option<String>,
+    pub compare: Option<String>,
+    /// In percent
+    pub threshold: u32,
+    pub history: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReportArgs {
+    pub part: Option<Part>,
+    pub case: Case,
+    /// `None` when the default input directory should be used
+    pub input_dir: Option<PathBuf>,
+    pub format: Format,
+    /// How long a part may take, `None` for no limit
+    pub timeout: Option<Duration>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NewDayArgs {
+    pub day: u32,
+    pub title: String,
+    /// `None` when the inputs go to the default directory
+    pub input_dir: Option<PathBuf>,
+}
+
+/// Counts the `-v`, `-vv`, ... and `--verbose` flags in front of the command and returns the
+/// arguments after them. Later arguments belong to the command, so they are left alone.
+pub fn verbosity(args: impl Iterator<Item = String>) -> (u8, Vec<String>) {
+    let mut verbosity = 0u8;
+    let mut args = args.peekable();
+    while let Some(arg) = args.peek() {
+        match arg.as_str() {
+            "--verbose" => verbosity = verbosity.saturating_add(1),
+            x if x.len() > 1
+                && x.strip_prefix('-')
+                    .is_some_and(|v| v.chars().all(|c| c == 'v')) =>
+            {
+                verbosity = verbosity.saturating_add(x.len() as u8 - 1);
+            }
+            _ => break,
+        }
+        args.next();
+    }
+    (verbosity, args.collect())
+}
+
+pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("run") => parse_run(args).map(Command::Run),
+        Some("bench") => parse_bench(args).map(Command::Bench),
+        Some("report") => parse_report(args).map(Command::Report),
+        Some("list") => Ok(Command::List),
+        Some("new-day") => parse_new_day(args).map(Command::NewDay),
+        Some("help" | "-h" | "--help") | None => Ok(Command::Help),
+        Some(x) => Err(format!("unknown command '{x}'")),
+    }
+}
+
+fn parse_run(mut args: impl Iterator<Item = String>) -> Result<RunArgs, String> {
+    let mut days = None;
+    let mut part = None;
+    let mut case = Case::Real;
+    let mut input = None;
+    let mut examples = false;
+    let mut cached = false;
+    let mut jobs = 1;
+    let mut timeout = None;
+
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{flag}'"))
+        };
+        match flag.as_str() {
+            "--day" | "-d" => {
+                let v = value()?;
+                let day = v.parse::<u32>().map_err(|_| format!("invalid day '{v}'"))?;
+                days = Some(Days::One(day));
+            }
+            "--all" | "-a" => days = Some(Days::All),
+            "--part" | "-p" => part = Some(value()?.parse()?),
+            "--case" | "-c" => case = value()?.parse()?,
+            "--examples" | "-e" => examples = true,
+            "--cached" => cached = true,
+            "--timeout" | "-t" => timeout = Some(seconds(value()?)?),
+            "--jobs" | "-j" => {
+                let v = value()?;
+                jobs = match v.parse::<usize>() {
+                    Ok(x) if x > 0 => x,
+                    _ => return Err(format!("invalid number of jobs '{v}'")),
+                };
+            }
+            "--input-dir" => input = Some(Input::Dir(PathBuf::from(value()?))),
+            "--input" | "-i" => {
+                input = match value()?.as_str() {
+                    "-" => Some(Input::Stdin),
+                    x => Some(Input::File(PathBuf::from(x))),
+                }
+            }
+            x => return Err(format!("unknown option '{x}'")),
+        }
+    }
+
+    let days = days.ok_or("missing '--day' or '--all'")?;
+    if days == Days::All && matches!(input, Some(Input::File(_) | Input::Stdin)) {
+        return Err("'--input' can only be used with '--day'".to_string());
+    }
+    if examples && matches!(input, Some(Input::File(_) | Input::Stdin)) {
+        return Err("'--examples' can not be combined with '--input'".to_string());
+    }
+    if cached && matches!(input, Some(Input::File(_) | Input::Stdin)) {
+        return Err("'--cached' can not be combined with '--input'".to_string());
+    }
+
+    Ok(RunArgs {
+        days,
+        part,
+        case,
+        examples,
+        input,
+        cached,
+        jobs,
+        timeout,
+    })
+}
+
+fn parse_bench(mut args: impl Iterator<Item = String>) -> Result<BenchArgs, String> {
+    let mut runs = 10;
+    let mut save = None;
+    let mut compare = None;
+    let mut threshold = 10;
+    let mut history = PathBuf::from(crate::bench::History::FILE_NAME);
+    let mut rest = vec![];
+
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{flag}'"))
+        };
+        match flag.as_str() {
+            "--runs" | "-r" => {
+                let v = value()?;
+                runs = match v.parse::<usize>() {
+                    Ok(x) if x > 0 => x,
+                    _ => return Err(format!("invalid number of runs '{v}'")),
+                };
+            }
+            "--save" => save = Some(label(value()?)?),
+            "--compare" => compare = Some(label(value()?)?),
+            "--threshold" => {
+                let v = value()?;
+                threshold = v
+                    .parse::<u32>()
+                    .map_err(|_| format!("invalid threshold '{v}'"))?;
+            }
+            "--history" => history = PathBuf::from(value()?),
+            _ => rest.push(flag),
+        }
+    }
+
+    let run = parse_run(rest.into_iter())?;
+    if run.examples {
+        return Err("'--examples' can not be used with bench".to_string());
+    }
+    if run.jobs != 1 {
+        return Err("'--jobs' can not be used with bench".to_string());
+    }
+    if run.cached {
+        return Err("'--cached' can not be used with bench".to_string());
+    }
+    if run.timeout.is_some() {
+        return Err("'--timeout' can not be used with bench".to_string());
+    }
+    Ok(BenchArgs {
+        run,
+        runs,
+        save,
+        compare,
+        threshold,
+        history,
+    })
+}
+
+fn parse_report(mut args: impl Iterator<Item = String>) -> Result<ReportArgs, String> {
+    let mut report = ReportArgs {
+        part: None,
+        case: Case::Real,
+        input_dir: None,
+        format: Format::Table,
+        timeout: None,
+    };
+
+    while let Some(flag) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or_else(|| format!("missing value for '{flag}'"))
+        };
+        match flag.as_str() {
+            "--part" | "-p" => report.part = Some(value()?.parse()?),
+            "--case" | "-c" => report.case = value()?.parse()?,
+            "--input-dir" => report.input_dir = Some(PathBuf::from(value()?)),
+            "--timeout" | "-t" => report.timeout = Some(seconds(value()?)?),
+            "--format" | "-f" => {
+                report.format = match value()?.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    x => return Err(format!("invalid format '{x}', expected table, json or csv")),
+                }
+            }
+            x => return Err(format!("unknown option '{x}'")),
+        }
+    }
+    Ok(report)
+}
+
+fn parse_new_day(mut args: impl Iterator<Item = String>) -> Result<NewDayArgs, String> {
+    let mut day = None;
+    let mut title = None;
+    let mut input_dir = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--title" | "-t" => {
+                title = Some(args.next().ok_or("missing value for '--title'")?);
+            }
+            "--input-dir" => {
+                let dir = args.next().ok_or("missing value for '--input-dir'")?;
+                input_dir = Some(PathBuf::from(dir));
+            }
+            x if day.is_none() => {
+                day = Some(x.parse::<u32>().map_err(|_| format!("invalid day '{x}'"))?);
+            }
+            x => return Err(format!("unexpected argument '{x}'")),
+        }
+    }
+
+    let day = day.ok_or("missing day")?;
+    Ok(NewDayArgs {
+        day,
+        title: title.unwrap_or_else(|| format!("Day {day}")),
+        input_dir,
+    })
+}
+
+/// A positive number of seconds, like `0.5`
+fn seconds(value: String) -> Result<Duration, String> {
+    match value.parse::<f64>().map(Duration::try_from_secs_f64) {
+        Ok(Ok(x)) if !x.is_zero() => Ok(x),
+        _ => Err(format!("invalid timeout '{value}'")),
+    }
+}
+
+fn label(value: String) -> Result<String, String> {
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        return Err(format!(
+            "invalid label '{value}', it can not contain whitespace"
+        ));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> impl Iterator<Item = String> + '_ {
+        s.split_whitespace().map(|x| x.to_string())
+    }
+
+    #[test]
+    fn parse_run_all_options() {
+        let cmd = parse(args("run --day 17 --part 2 --case test"));
+        assert_eq!(
+            cmd,
+            Ok(Command::Run(RunArgs {
+                days: Days::One(17),
+                part: Some(Part::Two),
+                case: Case::Test,
+                examples: false,
+                input: None,
+                cached: false,
+                jobs: 1,
+                timeout: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_run_defaults() {
+        let cmd = parse(args("run -d 3"));
+        assert_eq!(
+            cmd,
+            Ok(Command::Run(RunArgs {
+                days: Days::One(3),
+                part: None,
+                case: Case::Real,
+                examples: false,
+                input: None,
+                cached: false,
+                jobs: 1,
+                timeout: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_run_all() {
+        let cmd = parse(args("run --all --case test"));
+        assert_eq!(
+            cmd,
+            Ok(Command::Run(RunArgs {
+                days: Days::All,
+                part: None,
+                case: Case::Test,
+                examples: false,
+                input: None,
+                cached: false,
+                jobs: 1,
+                timeout: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_run_examples() {
+        let Ok(Command::Run(a)) = parse(args("run -d 17 --examples")) else {
+            panic!()
+        };
+        assert!(a.examples);
+
+        let Ok(Command::Run(a)) = parse(args("run -d 17 -c example:3")) else {
+            panic!()
+        };
+        assert_eq!(a.case, Case::Example("3".to_string()));
+    }
+
+    #[test]
+    fn parse_run_cached() {
+        let Ok(Command::Run(a)) = parse(args("run --all --cached")) else {
+            panic!()
+        };
+        assert!(a.cached);
+        assert_eq!(a.jobs, 1);
+
+        let Ok(Command::Run(a)) = parse(args("run --all -j 4")) else {
+            panic!()
+        };
+        assert_eq!(a.jobs, 4);
+        assert_eq!(a.timeout, None);
+
+        let Ok(Command::Run(a)) = parse(args("run --all --timeout 1.5")) else {
+            panic!()
+        };
+        assert_eq!(a.timeout, Some(Duration::from_millis(1500)));
+        assert!(parse(args("run -d 1 --cached -i x.txt")).is_err());
+    }
+
+    #[test]
+    fn parse_run_input() {
+        let Ok(Command::Run(a)) = parse(args("run -d 1 --input-dir /tmp/aoc")) else {
+            panic!()
+        };
+        assert_eq!(a.input, Some(Input::Dir(PathBuf::from("/tmp/aoc"))));
+
+        let Ok(Command::Run(a)) = parse(args("run -d 1 --input in.txt")) else {
+            panic!()
+        };
+        assert_eq!(a.input, Some(Input::File(PathBuf::from("in.txt"))));
+
+        let Ok(Command::Run(a)) = parse(args("run -d 1 -i -")) else {
+            panic!()
+        };
+        assert_eq!(a.input, Some(Input::Stdin));
+    }
+
+    #[test]
+    fn parse_bench_runs() {
+        let Ok(Command::Bench(a)) = parse(args("bench --all --runs 3 -c test")) else {
+            panic!()
+        };
+        assert_eq!(a.runs, 3);
+        assert_eq!(a.run.days, Days::All);
+        assert_eq!(a.run.case, Case::Test);
+
+        let Ok(Command::Bench(a)) = parse(args("bench -d 1")) else {
+            panic!()
+        };
+        assert_eq!(a.runs, 10);
+        assert_eq!(a.save, None);
+        assert_eq!(a.threshold, 10);
+    }
+
+    #[test]
+    fn parse_bench_history() {
+        let cmd = parse(args(
+            "bench -d 1 --save new --compare old --threshold 5 --history h.txt",
+        ));
+        let Ok(Command::Bench(a)) = cmd else { panic!() };
+        assert_eq!(a.save.as_deref(), Some("new"));
+        assert_eq!(a.compare.as_deref(), Some("old"));
+        assert_eq!(a.threshold, 5);
+        assert_eq!(a.history, PathBuf::from("h.txt"));
+    }
+
+    #[test]
+    fn parse_report() {
+        let cmd = parse(args("report -c test --format csv"));
+        assert_eq!(
+            cmd,
+            Ok(Command::Report(ReportArgs {
+                part: None,
+                case: Case::Test,
+                input_dir: None,
+                format: Format::Csv,
+                timeout: None,
+            }))
+        );
+        let Ok(Command::Report(a)) = parse(args("report")) else {
+            panic!()
+        };
+        assert_eq!(a.format, Format::Table);
+        let Ok(Command::Report(a)) = parse(args("report -t 1.5")) else {
+            panic!()
+        };
+        assert_eq!(a.timeout, Some(Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn parse_new_day() {
+        assert_eq!(
+            parse(args("new-day 9")),
+            Ok(Command::NewDay(NewDayArgs {
+                day: 9,
+                title: "Day 9".to_string(),
+                input_dir: None,
+            }))
+        );
+        let cmd = parse(
+            ["new-day", "--title", "Disk Fragmenter", "9"]
+                .map(String::from)
+                .into_iter(),
+        );
+        let Ok(Command::NewDay(a)) = cmd else {
+            panic!()
+        };
+        assert_eq!(a.title, "Disk Fragmenter");
+        let Ok(Command::NewDay(a)) = parse(args("new-day --input-dir inputs 9")) else {
+            panic!()
+        };
+        assert_eq!(a.input_dir, Some(PathBuf::from("inputs")));
+    }
+
+    #[test]
+    fn count_verbosity() {
+        let (v, rest) = verbosity(args("-v --verbose -vv run -d 1"));
+        assert_eq!(v, 4);
+        assert_eq!(rest, ["run", "-d", "1"]);
+        assert_eq!(verbosity(args("run -d 1")).0, 0);
+
+        // flags after the command are its own, even when they look like -v
+        let (v, rest) = verbosity(args("-v new-day 9 --title -v"));
+        assert_eq!(v, 1);
+        assert_eq!(rest, ["new-day", "9", "--title", "-v"]);
+        assert!(parse(args("run -d 1 -v")).is_err());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(parse(args("run")).is_err());
+        assert!(parse(args("run --day x")).is_err());
+        assert!(parse(args("run --day 1 --part 3")).is_err());
+        assert!(parse(args("run --day")).is_err());
+        assert!(parse(args("jump")).is_err());
+        assert!(parse(args("run --all --input -")).is_err());
+        assert!(parse(args("run -d 1 --examples --input x.txt")).is_err());
+        assert!(parse(args("bench -d 1 --runs 0")).is_err());
+        assert!(parse(args("run --all --jobs 0")).is_err());
+        assert!(parse(args("run --all --timeout -1")).is_err());
+        assert!(parse(args("bench --all --jobs 2")).is_err());
+        assert!(parse(args("bench -d 1 --examples")).is_err());
+        assert!(parse(args("bench -d 3 --timeout 1")).is_err());
+        assert!(parse(args("bench -d 3 --cached")).is_err());
+        assert!(parse(args("report --timeout 0")).is_err());
+        assert!(parse(args("report --format xml")).is_err());
+        assert!(parse(args("report --day 1")).is_err());
+        assert!(parse(args("new-day")).is_err());
+        assert!(parse(args("new-day 9 10")).is_err());
+        assert!(parse(args("bench -d 1 --threshold -5")).is_err());
+    }
+}
@@ -1,34 +1,23 @@
 use std::collections::HashMap;
 
-use crate::{ProblemData, Solver};
+use crate::{error::Error, ProblemData, Solver};
 
 pub struct Day1 {
-    data: ProblemData,
+    pairs: Vec<[i32; 2]>,
 }
 
 impl Solver for Day1 {
-    fn new(data: ProblemData) -> Self {
-        Self { data }
+    fn try_new(data: ProblemData) -> Result<Self, Error> {
+        let pairs = data
+            .numbered_lines()
+            .map(|x| x.list_n::<i32, 2>())
+            .collect::<Result<_, _>>()?;
+        Ok(Self { pairs })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
-        let mut left: Vec<i32> = vec![];
-        let mut right: Vec<i32> = vec![];
-        for x in self.data.iter() {
-            let a = x
-                .split_whitespace()
-                .map(|b| b.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>();
-
-            if let Some(x) = a.get(0) {
-                left.push(*x);
-            }
-            if let Some(x) = a.get(1) {
-                right.push(*x);
-            }
-        }
-
-        assert_eq!(left.len(), right.len());
+        let (mut left, mut right): (Vec<i32>, Vec<i32>) =
+            self.pairs.iter().map(|&[a, b]| (a, b)).unzip();
         left.sort();
         right.sort();
 
@@ -36,7 +25,7 @@ impl Solver for Day1 {
             .iter()
             .zip(right.iter())
             .map(|(&a, &b)| (a - b).abs())
-            .fold(0, |acc, diff| acc + diff);
+            .sum::<i32>();
 
         b
     }
@@ -44,20 +33,9 @@ impl Solver for Day1 {
     fn solve2(&mut self) -> impl Into<crate::Answer> {
         let mut left: Vec<i32> = vec![];
         let mut freq: HashMap<i32, i32> = HashMap::new();
-        for x in self.data.iter() {
-            let a = x
-                .split_whitespace()
-                .map(|b| b.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>();
-
-            if let Some(x) = a.get(0) {
-                left.push(*x);
-            }
-            if let Some(x) = a.get(1) {
-                if let Some(curr) = freq.get(x).or(Some(&0)) {
-                    let _ = freq.insert(*x, *curr + 1);
-                }
-            }
+        for &[a, b] in self.pairs.iter() {
+            left.push(a);
+            *freq.entry(b).or_insert(0) += 1;
         }
 
         let mut acc = 0;
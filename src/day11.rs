@@ -1,17 +1,18 @@
-use crate::Solver;
+use crate::{error::Error, Solver};
 
 pub struct Day11 {
     data: Vec<String>,
 }
 
 impl Solver for Day11 {
-    fn new(data: crate::ProblemData) -> Self {
-        if let Some(d) = data.data.first() {
-            let s = d.split(" ").map(|x| x.to_string()).collect::<Vec<String>>();
-            Self { data: s }
-        } else {
-            panic!("")
-        }
+    fn try_new(data: crate::ProblemData) -> Result<Self, Error> {
+        let Some(line) = data.numbered_lines().next() else {
+            return Err(Error::new("expected a line of stones"));
+        };
+        let stones = line.list::<u64>()?;
+        Ok(Self {
+            data: stones.iter().map(|x| x.to_string()).collect(),
+        })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
@@ -19,9 +20,8 @@ impl Solver for Day11 {
             let x = self
                 .data
                 .iter()
-                .map(|a| apply_rule(a.as_str()))
-                .flatten()
-                .filter(|v| v != "")
+                .flat_map(|a| apply_rule(a.as_str()))
+                .filter(|v| !v.is_empty())
                 .collect::<Vec<String>>();
             self.data = x;
         }
@@ -29,7 +29,7 @@ impl Solver for Day11 {
     }
 
     fn solve2(&mut self) -> impl Into<crate::Answer> {
-        ""
+        crate::Answer::Unsolved
     }
 }
 
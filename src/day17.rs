@@ -1,4 +1,4 @@
-use crate::Solver;
+use crate::{error::Error, log::debug, Solver};
 
 pub struct Day17 {
     pub state: State,
@@ -8,7 +8,7 @@ pub struct Day17 {
 impl Day17 {
     fn next(&self) -> (Option<Op>, Option<u32>) {
         let a = self.program.get(self.state.pointer).map(|x| Op::from(*x));
-        let b = self.program.get(self.state.pointer + 1).map(|x| *x);
+        let b = self.program.get(self.state.pointer + 1).copied();
         (a, b)
     }
 
@@ -24,7 +24,7 @@ impl Day17 {
             }
         }
 
-        return true;
+        true
     }
 
     fn reset_and_set_a(&mut self, value: u32) {
@@ -110,7 +110,7 @@ impl Day17 {
     }
     fn get_combo_operand(&self, operand: u32) -> u32 {
         match operand {
-            0 | 1 | 2 | 3 => operand,
+            0..=3 => operand,
             4 => self.state.a,
             5 => self.state.b,
             6 => self.state.c,
@@ -125,30 +125,30 @@ impl Day17 {
 }
 
 impl Solver for Day17 {
-    fn new(data: crate::ProblemData) -> Self {
-        let initial_state = data.data[0..=2]
-            .iter()
-            .map(|x| x.split_whitespace().last().unwrap().parse::<u32>().unwrap())
-            .collect::<Vec<u32>>();
+    fn try_new(data: crate::ProblemData) -> Result<Self, Error> {
+        let [registers, program] = &data.sections()[..] else {
+            return Err(Error::new(
+                "expected registers and a program separated by a blank line",
+            ));
+        };
+        let register = |name| registers.field(name).and_then(|x| x.parse::<u32>());
         let state = State {
-            a: initial_state[0],
-            b: initial_state[1],
-            c: initial_state[2],
+            a: register("Register A")?,
+            b: register("Register B")?,
+            c: register("Register C")?,
             pointer: 0,
             buffer: Vec::new(),
         };
 
-        let program = data.data[4]
-            .split_whitespace()
-            .last()
-            .unwrap()
-            .split(',')
-            .map(|x| x.parse::<u32>().unwrap())
-            .collect::<Vec<u32>>();
-
-        println!("{state:?}");
-        println!("{program:?}");
-        Self { state, program }
+        let line = program.field("Program")?;
+        let program = line.list::<u32>()?;
+        if let Some(x) = program.iter().find(|&&x| x >= 8) {
+            return Err(line.error(format!("invalid instruction '{x}'")));
+        }
+
+        debug!("{state:?}");
+        debug!("program {program:?}");
+        Ok(Self { state, program })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
@@ -156,17 +156,14 @@ impl Solver for Day17 {
     }
 
     fn solve2(&mut self) -> impl Into<crate::Answer> {
-        let mut i = 1;
-        loop {
-            if i == 100 {
-                return String::from("100");
-            }
+        // only small values of register A are tried, past those the search gives up
+        for i in 1..100 {
             self.reset_and_set_a(i);
             if let Some(x) = self.run2() {
-                return x;
+                return crate::Answer::from(x);
             }
-            i += 1;
         }
+        crate::Answer::Unsolved
     }
 }
 
@@ -1,7 +1,7 @@
-use crate::{ProblemData, Solver};
+use crate::{error::Error, ProblemData, Solver};
 
 pub struct Day2 {
-    data: ProblemData,
+    reports: Vec<Vec<i32>>,
 }
 
 enum Diff {
@@ -10,20 +10,14 @@ enum Diff {
     Unsafe,
 }
 
-fn to_diff(val: &String) -> Vec<Diff> {
-    let vals = val.split_whitespace().filter_map(|y| y.parse::<i32>().ok());
-    let next_vals = val
-        .split_whitespace()
-        .filter_map(|y| y.parse::<i32>().ok())
-        .skip(1);
-
-    vals.zip(next_vals)
-        .map(|(cur, next)| {
-            let diff = next - cur;
+fn to_diff(vals: &[i32]) -> Vec<Diff> {
+    vals.windows(2)
+        .map(|w| {
+            let diff = w[1] - w[0];
             match diff {
                 1..=3 => Diff::Decreasing,
                 -3..=-1 => Diff::Increasing,
-                x => Diff::Unsafe,
+                _ => Diff::Unsafe,
             }
         })
         .collect::<Vec<_>>()
@@ -50,7 +44,7 @@ fn to_diff(val: &String) -> Vec<Diff> {
 //         .collect::<Vec<_>>()
 // }
 
-fn all_same(vec: &Vec<Diff>) -> bool {
+fn all_same(vec: &[Diff]) -> bool {
     vec.iter().all(|y| matches!(y, Diff::Increasing))
         || vec.iter().all(|z| matches!(z, Diff::Decreasing))
 }
@@ -83,21 +77,16 @@ fn all_same(vec: &Vec<Diff>) -> bool {
 //     // (x.0 + x.2 == vec.len() || x.1 + x.2 == vec.len()) && matches!(x.2, 0..=1)
 // }
 
-fn all_inc(x: &Vec<i32>) -> bool {
-    x.windows(2)
-        .map(|x| x[1] - x[0] > 1 && x[1] - x[0] < 4)
-        .all(|x| x == true)
+fn all_inc(x: &[i32]) -> bool {
+    x.windows(2).all(|x| x[1] - x[0] > 1 && x[1] - x[0] < 4)
 }
 
-fn all_dec(x: &Vec<i32>) -> bool {
-    x.windows(2)
-        .map(|x| x[0] - x[1] > 1 && x[1] - x[0] < 4)
-        .all(|x| x == true)
+fn all_dec(x: &[i32]) -> bool {
+    x.windows(2).all(|x| x[0] - x[1] > 1 && x[1] - x[0] < 4)
 }
 
-fn variants(x: &Vec<i32>) -> Vec<Vec<i32>> {
+fn variants(_x: &[i32]) -> Vec<Vec<i32>> {
     let cur = vec![];
-    let mut res = vec![];
     // for i in 0..len {
     //     cur.push(
     //         x.iter()
@@ -108,33 +97,32 @@ fn variants(x: &Vec<i32>) -> Vec<Vec<i32>> {
     //             .collect(),
     //     )
     // }
-    res.push(cur);
-    res
+    vec![cur]
 }
 
-fn is_valid(x: &Vec<i32>) -> bool {
+fn is_valid(x: &[i32]) -> bool {
     all_inc(x) || all_dec(x) || variants(x).iter().any(|x| all_dec(x) || all_inc(x))
 }
 
 impl Solver for Day2 {
-    fn new(data: ProblemData) -> Self {
-        Self { data }
+    fn try_new(data: ProblemData) -> Result<Self, Error> {
+        let reports = data
+            .numbered_lines()
+            .map(|x| x.list::<i32>())
+            .collect::<Result<_, _>>()?;
+        Ok(Self { reports })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
-        self.data.iter().map(to_diff).filter(all_same).count()
+        self.reports
+            .iter()
+            .map(|x| to_diff(x))
+            .filter(|x| all_same(x))
+            .count()
     }
 
     fn solve2(&mut self) -> impl Into<crate::Answer> {
-        self.data
-            .iter()
-            .map(|x| {
-                x.split_whitespace()
-                    .filter_map(|y| y.parse::<i32>().ok())
-                    .collect()
-            })
-            .filter(|x| is_valid(x))
-            .count()
+        self.reports.iter().filter(|x| is_valid(x)).count()
     }
 }
 
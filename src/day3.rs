@@ -1,24 +1,17 @@
-use std::collections::VecDeque;
-
-use crate::{ProblemData, Solver};
+use crate::{error::Error, ProblemData, Solver};
 
 pub struct Day3 {
-    data: VecDeque<char>,
     ops: Vec<Operation>,
 }
 
 impl Solver for Day3 {
-    fn new(data: ProblemData) -> Self {
-        Self {
-            data: data.iter().flat_map(|c| c.chars()).collect(),
-            ops: vec![],
-        }
+    fn try_new(data: ProblemData) -> Result<Self, Error> {
+        Ok(Self {
+            ops: parse(data.as_str()),
+        })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
-        if self.ops.len() == 0 {
-            self.ops = self.parse();
-        }
         self.ops.iter().fold(0, |acc, x| match x {
             Operation::Mul(a, b) => acc + (a * b),
             _ => acc,
@@ -26,9 +19,6 @@ impl Solver for Day3 {
     }
 
     fn solve2(&mut self) -> impl Into<crate::Answer> {
-        if self.ops.len() == 0 {
-            self.ops = self.parse();
-        }
         let mut state = StateMachine {
             is_enabled: true,
             ops: &self.ops,
@@ -38,89 +28,49 @@ impl Solver for Day3 {
     }
 }
 
-impl Day3 {
-    fn take_expecting(&mut self, expecting: &str) -> bool {
-        let len = expecting.len();
-        let e: String = self.data.range(..len).cloned().collect();
-        if e == expecting {
-            self.data.drain(..len);
-            return true;
-        }
-        false
-    }
-
-    fn take_number(&mut self) -> Option<i32> {
-        let mut nr = vec![];
-        while let Some(x) = self.data.get(0) {
-            if char::is_digit(*x, 10) {
-                nr.push(self.data.pop_front().unwrap());
-            } else {
-                break;
-            }
-        }
-
-        return String::from_iter(nr).parse::<i32>().ok();
-    }
+fn take_number(s: &str) -> Option<(i32, &str)> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let nr = s[..end].parse::<i32>().ok()?;
+    Some((nr, &s[end..]))
+}
 
-    fn take_do_or_dont(&mut self) -> Option<Operation> {
-        let do_str: String = self.data.range(.."do()".len()).cloned().collect();
-        let dont_str: String = self.data.range(.."don't()".len()).cloned().collect();
+fn take_mul(s: &str) -> Option<(Operation, &str)> {
+    let s = s.strip_prefix("mul(")?;
+    let (a, s) = take_number(s)?;
+    let s = s.strip_prefix(',')?;
+    let (b, s) = take_number(s)?;
+    let s = s.strip_prefix(')')?;
+    Some((Operation::Mul(a, b), s))
+}
 
-        if do_str.as_str() == "do()" {
-            self.data.drain(..4);
-            return Some(Operation::Do);
-        } else if dont_str.as_str() == "don't()" {
-            self.data.drain(..7);
-            return Some(Operation::Dont);
-        } else {
-            return None;
-        }
+fn take_do_or_dont(s: &str) -> Option<(Operation, &str)> {
+    if let Some(s) = s.strip_prefix("do()") {
+        Some((Operation::Do, s))
+    } else {
+        s.strip_prefix("don't()").map(|s| (Operation::Dont, s))
     }
+}
 
-    fn parse(&mut self) -> Vec<Operation> {
-        let mut ops: Vec<Operation> = vec![];
-        while let Some(c) = self.data.get(0) {
-            let op = match c {
-                'm' => {
-                    if !self.take_expecting("mul(") {
-                        self.data.pop_front();
-                        continue;
-                    }
-                    let Some(a) = self.take_number() else {
-                        self.data.pop_front();
-                        continue;
-                    };
-                    if !self.take_expecting(",") {
-                        self.data.pop_front();
-                        continue;
-                    }
-                    let Some(b) = self.take_number() else {
-                        self.data.pop_front();
-                        continue;
-                    };
-                    if !self.take_expecting(")") {
-                        self.data.pop_front();
-                        continue;
-                    }
-                    Operation::Mul(a, b)
-                }
-                'd' => {
-                    let Some(dodont) = self.take_do_or_dont() else {
-                        self.data.pop_front();
-                        continue;
-                    };
-                    dodont
-                }
-                _ => {
-                    self.data.pop_front();
-                    continue;
-                }
-            };
-            ops.push(op);
+/// Scans the memory for operations, skipping anything that is corrupted
+fn parse(mut s: &str) -> Vec<Operation> {
+    let mut ops = vec![];
+    while let Some(c) = s.chars().next() {
+        let op = match c {
+            'm' => take_mul(s),
+            'd' => take_do_or_dont(s),
+            _ => None,
+        };
+        match op {
+            Some((op, rest)) => {
+                ops.push(op);
+                s = rest;
+            }
+            None => s = &s[c.len_utf8()..],
         }
-        ops
     }
+    ops
 }
+
 struct StateMachine<'a> {
     is_enabled: bool,
     ops: &'a Vec<Operation>,
@@ -1,31 +1,32 @@
-use crate::{grid::grid::Grid, Answer, Solver};
+use crate::{
+    error::Error,
+    grid::{Grid, GridDirection},
+    log::debug,
+    Answer, Solver,
+};
 
 pub struct Day4 {
     grid: Grid<char>,
 }
 
 impl Solver for Day4 {
-    fn new(data: crate::ProblemData) -> Self {
-        Self {
-            grid: Grid::from(data.data),
-        }
+    fn try_new(data: crate::ProblemData) -> Result<Self, Error> {
+        Ok(Self {
+            grid: Grid::parse(data.as_str(), Ok)?,
+        })
     }
 
     fn solve1(&mut self) -> impl Into<Answer> {
         let mut acc = 0;
-        for row in self.grid.iter_rows() {
-            for w in row.windows(4) {
-                let s: String = w.iter().map(|&c| *c).collect();
-                if s == "XMAS".to_string() || s == "SAMX".to_string() {
-                    acc += 1;
-                }
-            }
-        }
-        println!("{acc}");
-        for row in self.grid.iter_cols() {
-            for w in row.windows(4) {
-                let s: String = w.iter().map(|&c| *c).collect();
-                if s == "XMAS".to_string() || s == "SAMX".to_string() {
+        for (idx, _) in self.grid.indexed().filter(|(_, &c)| c == 'X') {
+            for dir in GridDirection::iter8() {
+                let word = self
+                    .grid
+                    .iter_from_index(idx, &dir)
+                    .take(4)
+                    .map(|(_, &c)| c);
+                if word.eq("XMAS".chars()) {
+                    debug!("XMAS at {idx:?} going {dir:?}");
                     acc += 1;
                 }
             }
@@ -34,6 +35,28 @@ impl Solver for Day4 {
     }
 
     fn solve2(&mut self) -> impl Into<Answer> {
-        0
+        Answer::Unsolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, ProblemData};
+
+    fn solve1(text: &str) -> Answer {
+        let data = ProblemData::from_string(text.to_string());
+        Day4::try_new(data).unwrap().solve1().into()
+    }
+
+    #[test]
+    fn diagonals_count() {
+        // the puzzle counts XMAS in all eight directions, diagonals and backwards included
+        assert_eq!(solve1("X...\n.M..\n..A.\n...S"), Answer::from(1));
+        assert_eq!(solve1("...S\n..A.\n.M..\nX..."), Answer::from(1));
+        assert_eq!(
+            solve1(include_str!("../data/day4_test.txt")),
+            Answer::from(18)
+        );
     }
 }
@@ -1,9 +1,6 @@
-use std::{
-    collections::{HashMap, HashSet},
-    str::FromStr,
-};
+use std::collections::{HashMap, HashSet};
 
-use crate::Solver;
+use crate::{error::Error, Solver};
 
 pub struct Day5 {
     rules: Vec<Rule>,
@@ -11,24 +8,32 @@ pub struct Day5 {
 }
 
 impl Solver for Day5 {
-    fn new(data: crate::ProblemData) -> Self {
-        let mut rules = Vec::new();
-        let mut updates = Vec::new();
-        for x in data.data.iter() {
-            if x.contains('|') {
-                rules.push(Rule::from_str(x).unwrap());
-            } else if x.len() == 0 {
-                continue;
-            } else {
-                updates.push(Update::from_str(x).unwrap());
-            }
-        }
-        Self { rules, updates }
+    fn try_new(data: crate::ProblemData) -> Result<Self, Error> {
+        let [rules, updates] = &data.sections()[..] else {
+            return Err(Error::new(
+                "expected rules and updates separated by a blank line",
+            ));
+        };
+        let rules = rules
+            .lines()
+            .map(|x| {
+                let mut fields = x.fields("{}|{}")?;
+                Ok(Rule {
+                    first: fields.value()?,
+                    second: fields.value()?,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        let updates = updates
+            .lines()
+            .map(|x| x.split(',').map(Update::from))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules, updates })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
         for u in self.updates.iter_mut() {
-            let _ = u.check_order(&self.rules);
+            u.check_order(&self.rules);
         }
 
         self.updates
@@ -39,7 +44,7 @@ impl Solver for Day5 {
 
     fn solve2(&mut self) -> impl Into<crate::Answer> {
         for u in self.updates.iter_mut() {
-            let _ = u.check_and_fix_order(&self.rules);
+            u.check_and_fix_order(&self.rules);
         }
 
         self.updates
@@ -55,26 +60,6 @@ struct Rule {
     second: usize,
 }
 
-#[derive(Debug)]
-struct ParseRuleErr;
-impl FromStr for Rule {
-    type Err = ParseRuleErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut a = s.split('|');
-        if let (Some(first), Some(second)) = (a.next(), a.next()) {
-            let f = first.parse::<usize>().map_err(|_| ParseRuleErr)?;
-            let s = second.parse::<usize>().map_err(|_| ParseRuleErr)?;
-
-            return Ok(Rule {
-                first: f,
-                second: s,
-            });
-        }
-        Err(ParseRuleErr)
-    }
-}
-
 struct Update {
     list: Vec<usize>,
     indexed: HashMap<usize, usize>,
@@ -88,7 +73,7 @@ impl Update {
         *self.list.get(middle).unwrap_or(&0usize)
     }
 
-    fn check_order(&mut self, rules: &Vec<Rule>) {
+    fn check_order(&mut self, rules: &[Rule]) {
         for x in rules.iter() {
             let u1 = self.indexed.get(&x.first);
             let u2 = self.indexed.get(&x.second);
@@ -103,10 +88,10 @@ impl Update {
         self.is_valid = true;
     }
 
-    fn check_and_fix_order(&mut self, rules: &Vec<Rule>) {
+    fn check_and_fix_order(&mut self, rules: &[Rule]) {
         for x in rules.iter() {
-            let u1 = self.indexed.get(&x.first).map(|v| *v);
-            let u2 = self.indexed.get(&x.second).map(|v| *v);
+            let u1 = self.indexed.get(&x.first).copied();
+            let u2 = self.indexed.get(&x.second).copied();
 
             if let (Some(u1), Some(u2)) = (u1, u2) {
                 //the index u1 is greater than u2 so it has to be in the wrong spot
@@ -147,24 +132,17 @@ impl Update {
     }
 }
 
-#[derive(Debug)]
-struct ParseUpdateErr;
-impl FromStr for Update {
-    type Err = ParseUpdateErr;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut u = Update {
-            list: Vec::with_capacity(s.len()),
-            indexed: HashMap::new(),
+impl From<Vec<usize>> for Update {
+    fn from(list: Vec<usize>) -> Self {
+        let mut indexed = HashMap::new();
+        for (i, &key) in list.iter().enumerate() {
+            indexed.entry(key).or_insert(i);
+        }
+        Update {
+            list,
+            indexed,
             is_valid: false,
             is_updated: false,
-        };
-
-        for (i, x) in s.split(',').enumerate() {
-            let key = x.parse::<usize>().unwrap();
-            u.list.push(key);
-            u.indexed.entry(key).or_insert(i);
         }
-        Ok(u)
     }
 }
@@ -1,87 +1,75 @@
-use std::{collections::HashSet, iter::Peekable};
+use std::collections::HashSet;
 
 use crate::{
-    grid::grid2::{Grid2, Grid2Iterator, GridDirection},
+    error::Error,
+    grid::{Grid, GridDirection, Point, Vector},
+    log::trace,
     Solver,
 };
 pub struct Day6 {
-    grid: Grid2<Node>,
-    map: HashSet<(usize, usize)>,
+    grid: Grid<Node>,
+    /// Where the guard starts and which way it faces
+    start: (Point, GridDirection),
+    map: HashSet<Point>,
 }
 
 #[derive(PartialEq, Eq)]
 pub enum Node {
-    Up,
-    Down,
-    Left,
-    Right,
+    Guard(GridDirection),
     Free,
     Obs,
 }
 
-impl From<char> for Node {
-    fn from(x: char) -> Self {
+impl TryFrom<char> for Node {
+    type Error = char;
+
+    fn try_from(x: char) -> Result<Self, Self::Error> {
         match x {
-            '^' => Self::Up,
-            'V' | 'v' => Self::Down,
-            '<' => Self::Left,
-            '>' => Self::Right,
-            '.' => Self::Free,
-            '#' => Self::Obs,
-            _ => panic!("invalid char"),
+            '^' | 'V' | 'v' | '<' | '>' => GridDirection::try_from(x).map(Self::Guard),
+            '.' => Ok(Self::Free),
+            '#' => Ok(Self::Obs),
+            x => Err(x),
         }
     }
 }
-fn is_start(x: &Node) -> bool {
-    match x {
-        Node::Up | Node::Down | Node::Left | Node::Right => true,
-        _ => false,
-    }
-}
 
 impl Day6 {
-    fn is_heading_off_grid(&self, idx: (usize, usize), dir: &GridDirection) -> bool {
-        match (idx, dir) {
-            ((0, _), &GridDirection::North) => true,
-            ((_, 0), &GridDirection::West) => true,
-            ((r, _), &GridDirection::South) if r == self.grid.row_len() - 1 => true,
-            ((_, c), &GridDirection::East) if c == self.grid.col_len() - 1 => true,
-            _ => false,
-        }
+    fn is_heading_off_grid(&self, p: Point, dir: &GridDirection) -> bool {
+        !self.grid.contains(p + Vector::from(*dir))
     }
 }
 
 impl Solver for Day6 {
-    fn new(data: crate::ProblemData) -> Self {
-        let d = data
-            .data
-            .iter()
-            .map(|s| s.chars().map(|c| Node::from(c)).collect::<Vec<_>>())
-            .collect::<Vec<Vec<_>>>();
-        Self {
-            grid: Grid2::new(d),
+    fn try_new(data: crate::ProblemData) -> Result<Self, Error> {
+        let grid = Grid::parse(data.as_str(), |c| {
+            Node::try_from(c).map_err(|c| format!("invalid char '{c}'"))
+        })?;
+        let guards = grid
+            .indexed()
+            .filter_map(|(p, n)| match n {
+                Node::Guard(dir) => Some((p, *dir)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let start = match guards[..] {
+            [start] => start,
+            [] => return Err(Error::new("no guard on the map")),
+            [_, (p, _), ..] => return Err(Error::at(p.y as usize, p.x as usize, "second guard")),
+        };
+        Ok(Self {
+            grid,
+            start,
             map: HashSet::new(),
-        }
+        })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
-        let mut start = (0usize, 0usize);
-        let mut dir = GridDirection::South;
-        for (row, nodes) in self.grid.iter().enumerate() {
-            for (col, n) in nodes.iter().enumerate() {
-                if is_start(n) {
-                    start = (row, col);
-                    dir = GridDirection::from(n);
-                    break;
-                }
-            }
-        }
-        assert_ne!((0, 0), start);
+        let (start, mut dir) = self.start;
 
         let mut pos = Some(start);
 
         while let Some(p) = pos {
-            println!("{p:?}");
+            trace!("guard at {p:?}");
             let it = self.grid.iter_from_index(p, &dir);
             for (idx, n) in it {
                 if n == &Node::Obs {
@@ -100,22 +88,23 @@ impl Solver for Day6 {
     }
 
     fn solve2(&mut self) -> impl Into<crate::Answer> {
-        0
+        crate::Answer::Unsolved
     }
 }
 
+#[allow(dead_code)]
 pub trait Find<U>
 where
     U: PartialEq,
 {
-    fn find_node(&mut self, pred: fn(&U) -> bool) -> impl Iterator<Item = Option<(usize, usize)>>;
+    fn find_node(&mut self, pred: fn(&U) -> bool) -> impl Iterator<Item = Option<Point>>;
 }
 
-// impl<T> Find<T> for Peekable<Grid2Iterator<'_, T>>
+// impl<T> Find<T> for Peekable<GridIterator<'_, T>>
 // where
 //     T: PartialEq + Eq,
 // {
-//     fn find_node(&mut self, pred: fn(&T) -> bool) -> impl Iterator<Item = <(usize, usize)>> {
+//     fn find_node(&mut self, pred: fn(&T) -> bool) -> impl Iterator<Item = <Point>> {
 //         // TODO: Peek
 //
 //         // }
@@ -129,15 +118,27 @@ where
 //     }
 // }
 
-impl From<&Node> for GridDirection {
-    fn from(value: &Node) -> Self {
-        match value {
-            Node::Up => GridDirection::North,
-            Node::Down => GridDirection::South,
-            Node::Left => Self::West,
-            Node::Right => Self::East,
-            Node::Free => panic!(),
-            Node::Obs => panic!(),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProblemData;
+
+    #[test]
+    fn guard_is_checked() {
+        let parse = |text: &str| Day6::try_new(ProblemData::from_string(text.to_string()));
+        assert_eq!(
+            parse("..#\n...").err(),
+            Some(Error::new("no guard on the map"))
+        );
+        assert_eq!(
+            parse(".^#\n..<").err(),
+            Some(Error::at(1, 2, "second guard"))
+        );
+        assert_eq!(
+            parse(".^#\n..").err(),
+            Some(Error::at_line(1, "expected 3 columns, found 2"))
+        );
+        let day = parse("...\n.>#").unwrap();
+        assert_eq!(day.start, (Point::new(1, 1), GridDirection::East));
     }
 }
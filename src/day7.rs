@@ -1,4 +1,4 @@
-use crate::Solver;
+use crate::{error::Error, Solver};
 #[derive(Debug)]
 pub struct Day7 {
     tests: Vec<Test>,
@@ -11,34 +11,26 @@ struct Test {
 }
 
 impl Solver for Day7 {
-    fn new(data: crate::ProblemData) -> Self {
+    fn try_new(data: crate::ProblemData) -> Result<Self, Error> {
         let tests = data
-            .iter()
+            .numbered_lines()
             .map(|x| {
-                if let Some((t, r)) = x.split_once(":") {
-                    let t = t.parse::<u64>().expect("cant parse number");
-                    let rest = r
-                        .split(" ")
-                        .filter_map(|x| x.trim().parse::<u64>().ok())
-                        .collect::<Vec<u64>>();
-                    Test {
-                        target: t,
-                        numbers: rest,
-                    }
-                } else {
-                    panic!("adf")
-                }
+                let mut fields = x.fields("{target}:{numbers}")?;
+                Ok(Test {
+                    target: fields.value()?,
+                    numbers: fields.list()?,
+                })
             })
-            .collect::<Vec<Test>>();
+            .collect::<Result<Vec<Test>, Error>>()?;
 
-        Self { tests }
+        Ok(Self { tests })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
         let mut answer = 0;
 
         for x in self.tests.iter() {
-            if can_solve(&x, 0, 0) {
+            if can_solve(x, 0, 0) {
                 answer += x.target;
             }
         }
@@ -46,7 +38,7 @@ impl Solver for Day7 {
     }
 
     fn solve2(&mut self) -> impl Into<crate::Answer> {
-        0
+        crate::Answer::Unsolved
     }
 }
 
@@ -59,6 +51,20 @@ fn can_solve(t: &Test, idx: usize, current: u64) -> bool {
         return false;
     }
     let curr_mul = if current == 0 { 1 } else { current };
-    can_solve(&t, idx + 1, current + t.numbers[idx])
-        || can_solve(&t, idx + 1, curr_mul * t.numbers[idx])
+    can_solve(t, idx + 1, current + t.numbers[idx])
+        || can_solve(t, idx + 1, curr_mul * t.numbers[idx])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ProblemData;
+
+    #[test]
+    fn parse_error_has_line() {
+        let data =
+            ProblemData::from_vec(vec!["190: 10 19".to_string(), "3267 81 40 27".to_string()]);
+        let e = Day7::try_new(data).unwrap_err();
+        assert_eq!(e, Error::at(1, 13, "expected ':'"));
+    }
 }
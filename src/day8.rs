@@ -1,125 +1,10 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::Solver;
-
-#[derive(Eq, Hash, PartialEq, Debug, Clone, Copy)]
-struct Idx {
-    x: i32,
-    y: i32,
-}
-
-#[derive(Copy, Clone)]
-struct IdxOffset(i32, i32);
-
-impl std::ops::Add<IdxOffset> for Idx {
-    type Output = Idx;
-
-    fn add(self, rhs: IdxOffset) -> Self::Output {
-        Self {
-            x: self.x + rhs.0,
-            y: self.y + rhs.1,
-        }
-    }
-}
-
-impl std::ops::Add<IdxOffset> for &Idx {
-    type Output = Idx;
-
-    fn add(self, rhs: IdxOffset) -> Self::Output {
-        Self::Output {
-            x: self.x + rhs.0,
-            y: self.y + rhs.1,
-        }
-    }
-}
-
-impl std::ops::Sub<IdxOffset> for Idx {
-    type Output = Idx;
-
-    fn sub(self, rhs: IdxOffset) -> Self::Output {
-        Self {
-            x: self.x - rhs.0,
-            y: self.y - rhs.1,
-        }
-    }
-}
-
-impl std::ops::Sub<IdxOffset> for &Idx {
-    type Output = Idx;
-
-    fn sub(self, rhs: IdxOffset) -> Self::Output {
-        Self::Output {
-            x: self.x - rhs.0,
-            y: self.y - rhs.1,
-        }
-    }
-}
-
-impl Ord for Idx {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        if self.y < other.y {
-            return std::cmp::Ordering::Less;
-        } else if self.y > other.y {
-            return std::cmp::Ordering::Greater;
-        } else if self.y == other.y && self.x < other.x {
-            return std::cmp::Ordering::Less;
-        } else if self.y == other.y && self.x > other.x {
-            return std::cmp::Ordering::Greater;
-        } else {
-            return std::cmp::Ordering::Equal;
-        }
-    }
-}
-
-impl PartialOrd for Idx {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl From<(i32, i32)> for Idx {
-    fn from(value: (i32, i32)) -> Self {
-        Self {
-            x: value.0,
-            y: value.1,
-        }
-    }
-}
-
-impl From<(usize, usize)> for Idx {
-    fn from(value: (usize, usize)) -> Self {
-        Self {
-            x: value.0 as i32,
-            y: value.1 as i32,
-        }
-    }
-}
-
-impl Idx {
-    fn is_valid<const N: usize>(&self) -> bool {
-        self.x >= 0 && self.x < N as i32 && self.y >= 0 && self.y < N as i32
-    }
-
-    fn offset_to(&self, other: &Idx) -> IdxOffset {
-        IdxOffset(self.x - other.x, self.y - other.y)
-    }
-
-    fn next_from_offset<const N: usize>(&self, offset: &IdxOffset) -> Option<Idx> {
-        let n = self + *offset;
-        if n.is_valid::<N>() {
-            return Some(n);
-        }
-        None
-    }
-
-    fn prev_from_offset<const N: usize>(&self, offset: &IdxOffset) -> Option<Idx> {
-        let n = self - *offset;
-        if n.is_valid::<N>() {
-            return Some(n);
-        }
-        None
-    }
-}
+use crate::{
+    error::Error,
+    grid::{Grid, Point},
+    Solver,
+};
 
 #[derive(Eq, PartialEq, Hash, Debug)]
 struct Antenna(char);
@@ -136,49 +21,49 @@ impl From<char> for Antenna {
     }
 }
 
-struct Roof<const N: usize> {
-    map: HashMap<Antenna, Vec<Idx>>,
-    taken_spots: HashSet<Idx>,
-    weakspots: HashSet<Idx>,
+struct Roof {
+    grid: Grid<char>,
+    map: HashMap<Antenna, Vec<Point>>,
+    taken_spots: HashSet<Point>,
+    weakspots: HashSet<Point>,
 }
 
-impl<const N: usize> From<crate::ProblemData> for Roof<N> {
-    fn from(value: crate::ProblemData) -> Self {
+impl From<Grid<char>> for Roof {
+    fn from(grid: Grid<char>) -> Self {
         let mut s = Self {
             map: HashMap::new(),
             weakspots: HashSet::new(),
             taken_spots: HashSet::new(),
+            grid,
         };
-        for (x, r) in value.iter().enumerate() {
-            for (y, c) in r.chars().enumerate() {
-                if c != '.' {
-                    s.map
-                        .entry(c.into())
-                        .and_modify(|m| m.push(Idx::from((x, y))))
-                        .or_insert(vec![Idx::from((x, y))]);
-                    s.taken_spots.insert(Idx::from((x, y)));
-                }
+        for (p, &c) in s.grid.indexed() {
+            if c != '.' {
+                s.map
+                    .entry(c.into())
+                    .and_modify(|m| m.push(p))
+                    .or_insert(vec![p]);
+                s.taken_spots.insert(p);
             }
         }
         s
     }
 }
 
-impl<const N: usize> Roof<N> {
+impl Roof {
     fn map_weakspots(&mut self) {
-        for (_, v) in &self.map {
+        for v in self.map.values() {
             if v.len() > 1 {
                 for first in v {
                     for second in v {
                         if first != second {
-                            let offset = first.offset_to(second);
-                            let first_w = first + offset;
-                            let second_w = second - offset;
-                            if Idx::is_valid::<N>(&first_w) {
+                            let offset = *first - *second;
+                            let first_w = *first + offset;
+                            let second_w = *second - offset;
+                            if self.grid.contains(first_w) {
                                 self.weakspots.insert(first_w);
                             }
 
-                            if Idx::is_valid::<N>(&second_w) {
+                            if self.grid.contains(second_w) {
                                 self.weakspots.insert(second_w);
                             }
                         }
@@ -189,23 +74,24 @@ impl<const N: usize> Roof<N> {
     }
 
     fn map_weakspots2(&mut self) {
-        for (_, v) in &self.map {
+        for v in self.map.values() {
             if v.len() > 1 {
-                for first in v.into_iter() {
+                for first in v.iter() {
                     for second in v {
                         if first != second {
-                            let offset = first.offset_to(second);
+                            // every position on the line counts, also those between the two
+                            let step = (*first - *second).normalized();
 
                             let mut f = *first;
-                            while let Some(n) = f.prev_from_offset::<N>(&offset) {
-                                self.weakspots.insert(n);
-                                f = n;
+                            while self.grid.contains(f) {
+                                self.weakspots.insert(f);
+                                f -= step;
                             }
 
-                            let mut s = *second;
-                            while let Some(p) = s.next_from_offset::<N>(&offset) {
-                                self.weakspots.insert(p);
-                                s = p;
+                            let mut s = *first + step;
+                            while self.grid.contains(s) {
+                                self.weakspots.insert(s);
+                                s += step;
                             }
                         }
                     }
@@ -219,7 +105,7 @@ impl<const N: usize> Roof<N> {
     }
 
     #[allow(dead_code)]
-    fn find_antenna_from_idx(&self, idx: &Idx) -> Option<&Antenna> {
+    fn find_antenna_from_idx(&self, idx: &Point) -> Option<&Antenna> {
         for (k, v) in self.map.iter() {
             if v.iter().any(|x| x == idx) {
                 return Some(k);
@@ -229,10 +115,10 @@ impl<const N: usize> Roof<N> {
     }
 
     #[allow(dead_code)]
-    fn pretty_print(&self, show_weakspots: bool) -> () {
-        for x in 0..N {
-            for y in 0..N {
-                let idx = Idx::from((x, y));
+    fn pretty_print(&self, show_weakspots: bool) {
+        for row in 0..self.grid.height() {
+            for col in 0..self.grid.width() {
+                let idx = Point::new(col as i32, row as i32);
                 let w = self.weakspots.get(&idx);
                 let a = self.find_antenna_from_idx(&idx);
                 match (w, a) {
@@ -249,13 +135,20 @@ impl<const N: usize> Roof<N> {
 }
 
 pub struct Day8 {
-    roof: Roof<50>,
+    roof: Roof,
 }
 
 impl Solver for Day8 {
-    fn new(data: crate::ProblemData) -> Self {
-        let r = Roof::<50>::from(data);
-        Self { roof: r }
+    fn try_new(data: crate::ProblemData) -> Result<Self, Error> {
+        // antennas are marked by a letter or digit, everything else is empty roof
+        let grid = Grid::parse(data.as_str(), |c| match c {
+            '.' => Ok(c),
+            c if c.is_ascii_alphanumeric() => Ok(c),
+            c => Err(format!("invalid char '{c}'")),
+        })?;
+        Ok(Self {
+            roof: Roof::from(grid),
+        })
     }
 
     fn solve1(&mut self) -> impl Into<crate::Answer> {
@@ -270,21 +163,22 @@ impl Solver for Day8 {
 }
 
 #[cfg(test)]
-mod test {
-
+mod tests {
     use super::*;
-    #[test]
-    fn test_idx_ord_lt() {
-        let a = Idx { x: 10, y: 1 };
-        let b = Idx { x: 1, y: 10 };
+    use crate::ProblemData;
 
-        assert!(a < b)
-    }
     #[test]
-    fn test_idx_ord_gt() {
-        let a = Idx { x: 10, y: 1 };
-        let b = Idx { x: 1, y: 10 };
-
-        assert!(b > a)
+    fn roof_is_checked() {
+        let parse = |text: &str| Day8::try_new(ProblemData::from_string(text.to_string()));
+        assert_eq!(
+            parse("a..\n.#.").err(),
+            Some(Error::at(1, 1, "invalid char '#'"))
+        );
+        assert_eq!(
+            parse("a..\n..\n..a").err(),
+            Some(Error::at_line(1, "expected 3 columns, found 2"))
+        );
+        let mut day = parse("....\n.a..\n..a.\n....").unwrap();
+        assert_eq!(day.solve1().into(), crate::Answer::from(2));
     }
 }
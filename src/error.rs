@@ -0,0 +1,101 @@
+use std::fmt::Display;
+
+/// An error while reading or parsing puzzle input, with the location it was found at
+///
+/// `line` and `col` are zero based indexes, as returned by `enumerate`, and are printed one
+/// based so they match what an editor shows.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Error {
+    pub source: Option<String>,
+    pub line: Option<usize>,
+    pub col: Option<usize>,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            source: None,
+            line: None,
+            col: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at_line(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            ..Self::new(message)
+        }
+    }
+
+    pub fn at(line: usize, col: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            col: Some(col),
+            ..Self::new(message)
+        }
+    }
+
+    /// Sets the file the error came from, unless it is already known
+    pub fn with_source(mut self, source: Option<&str>) -> Self {
+        if self.source.is_none() {
+            self.source = source.map(|x| x.to_string());
+        }
+        self
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(source) = &self.source {
+            write!(f, "{source}:")?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line + 1)?;
+            if let Some(col) = self.col {
+                write!(f, "{}:", col + 1)?;
+            }
+        }
+        if self.source.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::new(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_with_location() {
+        let e = Error::at_line(11, "expected ':'").with_source(Some("data/day7.txt"));
+        assert_eq!(e.to_string(), "data/day7.txt:12: expected ':'");
+
+        let e = Error::at(0, 4, "invalid char 'x'").with_source(Some("data/day6.txt"));
+        assert_eq!(e.to_string(), "data/day6.txt:1:5: invalid char 'x'");
+    }
+
+    #[test]
+    fn display_without_location() {
+        assert_eq!(Error::new("empty input").to_string(), "empty input");
+        assert_eq!(Error::at_line(2, "bad").to_string(), "3: bad");
+    }
+
+    #[test]
+    fn source_is_not_overwritten() {
+        let e = Error::new("x")
+            .with_source(Some("a.txt"))
+            .with_source(Some("b.txt"));
+        assert_eq!(e.source.as_deref(), Some("a.txt"));
+    }
+}
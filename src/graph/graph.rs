@@ -22,5 +22,5 @@ where
         self.vert.insert(id, ());
     }
 
-    pub fn push_edge(&mut self, id: Tid, edge: E) {}
+    pub fn push_edge(&mut self, _id: Tid, _edge: E) {}
 }
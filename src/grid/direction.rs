@@ -0,0 +1,198 @@
+/// A direction in a 2D grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridDirection {
+    South,
+    North,
+    West,
+    East,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl GridDirection {
+    /// The four straight directions once, clockwise starting at east
+    pub fn iter() -> impl Iterator<Item = GridDirection> {
+        GridDirectionIter {
+            current: GridDirection::North,
+            first: GridDirection::North,
+            seen_all: false,
+        }
+    }
+
+    /// All eight directions once, clockwise starting at east
+    pub fn iter8() -> impl Iterator<Item = GridDirection> {
+        [
+            GridDirection::East,
+            GridDirection::SouthEast,
+            GridDirection::South,
+            GridDirection::SouthWest,
+            GridDirection::West,
+            GridDirection::NorthWest,
+            GridDirection::North,
+            GridDirection::NorthEast,
+        ]
+        .into_iter()
+    }
+
+    /// Turns a quarter clockwise, diagonals stay diagonal
+    pub fn turn_right(&self) -> Self {
+        match self {
+            GridDirection::South => GridDirection::West,
+            GridDirection::North => GridDirection::East,
+            GridDirection::West => GridDirection::North,
+            GridDirection::East => GridDirection::South,
+            GridDirection::NorthEast => GridDirection::SouthEast,
+            GridDirection::SouthEast => GridDirection::SouthWest,
+            GridDirection::SouthWest => GridDirection::NorthWest,
+            GridDirection::NorthWest => GridDirection::NorthEast,
+        }
+    }
+
+    /// Turns a quarter counter-clockwise
+    #[allow(dead_code)]
+    pub fn turn_left(&self) -> Self {
+        self.turn_right().opposite()
+    }
+
+    /// Points the other way
+    #[allow(dead_code)]
+    pub fn opposite(&self) -> Self {
+        match self {
+            GridDirection::South => GridDirection::North,
+            GridDirection::North => GridDirection::South,
+            GridDirection::West => GridDirection::East,
+            GridDirection::East => GridDirection::West,
+            GridDirection::NorthEast => GridDirection::SouthWest,
+            GridDirection::SouthWest => GridDirection::NorthEast,
+            GridDirection::NorthWest => GridDirection::SouthEast,
+            GridDirection::SouthEast => GridDirection::NorthWest,
+        }
+    }
+
+    /// Turns `quarters` times clockwise, or counter-clockwise when negative
+    #[allow(dead_code)]
+    pub fn rotate(&self, quarters: i32) -> Self {
+        (0..quarters.rem_euclid(4)).fold(*self, |dir, _| dir.turn_right())
+    }
+
+    /// The change in `(row, col)` of one step
+    pub fn offset(&self) -> (i32, i32) {
+        match self {
+            GridDirection::North => (-1, 0),
+            GridDirection::South => (1, 0),
+            GridDirection::West => (0, -1),
+            GridDirection::East => (0, 1),
+            GridDirection::NorthEast => (-1, 1),
+            GridDirection::NorthWest => (-1, -1),
+            GridDirection::SouthEast => (1, 1),
+            GridDirection::SouthWest => (1, -1),
+        }
+    }
+}
+
+/// Reads `^v<>` arrows, `NSEW` compass points and `UDLR` as straight directions
+impl TryFrom<char> for GridDirection {
+    type Error = char;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' | 'U' => Ok(GridDirection::North),
+            'v' | 'V' | 'S' | 'D' => Ok(GridDirection::South),
+            '<' | 'W' | 'L' => Ok(GridDirection::West),
+            '>' | 'E' | 'R' => Ok(GridDirection::East),
+            c => Err(c),
+        }
+    }
+}
+
+/// An iterator over grid directions
+struct GridDirectionIter {
+    current: GridDirection,
+    first: GridDirection,
+    seen_all: bool,
+}
+
+impl Iterator for GridDirectionIter {
+    type Item = GridDirection;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.seen_all {
+            return None;
+        }
+        let res = self.current.turn_right();
+        self.current = res;
+        if self.current == self.first {
+            self.seen_all = true;
+        }
+        Some(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_static_dir_iter() {
+        let mut it = GridDirection::iter();
+        assert_eq!(it.next(), Some(GridDirection::East));
+        assert_eq!(it.next(), Some(GridDirection::South));
+        assert_eq!(it.next(), Some(GridDirection::West));
+        assert_eq!(it.next(), Some(GridDirection::North));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn eight_directions() {
+        let all = GridDirection::iter8().collect::<Vec<_>>();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[1], GridDirection::SouthEast);
+        // every step is different and moves
+        let mut steps = all.iter().map(|d| d.offset()).collect::<Vec<_>>();
+        steps.sort();
+        steps.dedup();
+        assert_eq!(steps.len(), 8);
+        assert!(!steps.contains(&(0, 0)));
+        assert_eq!(
+            GridDirection::NorthWest.turn_right(),
+            GridDirection::NorthEast
+        );
+    }
+
+    #[test]
+    fn turns() {
+        for dir in GridDirection::iter8() {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.rotate(2), dir.opposite());
+            assert_eq!(dir.rotate(-1), dir.turn_left());
+            assert_eq!(dir.rotate(5), dir.turn_right());
+            let (drow, dcol) = dir.offset();
+            assert_eq!(dir.opposite().offset(), (-drow, -dcol));
+        }
+        assert_eq!(GridDirection::North.turn_left(), GridDirection::West);
+        assert_eq!(
+            GridDirection::SouthEast.turn_left(),
+            GridDirection::NorthEast
+        );
+    }
+
+    #[test]
+    fn from_chars() {
+        let parse = |s: &str| {
+            s.chars()
+                .map(GridDirection::try_from)
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let expected = vec![
+            GridDirection::North,
+            GridDirection::South,
+            GridDirection::West,
+            GridDirection::East,
+        ];
+        assert_eq!(parse("^v<>"), Ok(expected.clone()));
+        assert_eq!(parse("NSWE"), Ok(expected.clone()));
+        assert_eq!(parse("UDLR"), Ok(expected));
+        assert_eq!(GridDirection::try_from('x'), Err('x'));
+    }
+}
@@ -1,82 +1,282 @@
 #![allow(dead_code)]
-pub struct Grid<T>
-where
-    T: Clone + std::fmt::Debug,
-{
-    data: Vec<Vec<T>>,
+use std::ops::{Index, IndexMut};
+
+use crate::{
+    error::Error,
+    grid::{GridDirection, Point, Vector},
+};
+
+/// A rectangle of values, stored row by row in one `Vec`
+///
+/// Positions are [`Point`]s, with the origin in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
 }
-/**
-     1 2 3 4 5 6 7 8 9
-*/
-impl<T> Grid<T>
-where
-    T: Clone + std::fmt::Debug,
-{
-    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
-        self.data.get(row).and_then(|r| r.get(col))
+
+impl<T> Grid<T> {
+    /// Fails if the rows don't all have the same length
+    pub fn new(rows: Vec<Vec<T>>) -> Result<Self, Error> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        if let Some((row, r)) = rows.iter().enumerate().find(|(_, r)| r.len() != width) {
+            return Err(Error::at_line(
+                row,
+                format!("expected {width} columns, found {}", r.len()),
+            ));
+        }
+        Ok(Self {
+            data: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
     }
-    pub fn iter_rows(&self) -> impl Iterator<Item = Vec<&T>> {
-        self.data.iter().map(|r| r.iter().collect())
+
+    /// Parses every character of `text` with `f`, one row per line
+    pub fn parse(text: &str, f: impl Fn(char) -> Result<T, String>) -> Result<Self, Error> {
+        let mut data = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (row, line) in text.lines().enumerate() {
+            let len = line.chars().count();
+            match width {
+                Some(w) if w != len => {
+                    return Err(Error::at_line(
+                        row,
+                        format!("expected {w} columns, found {len}"),
+                    ))
+                }
+                _ => width = Some(len),
+            }
+            for (col, c) in line.chars().enumerate() {
+                data.push(f(c).map_err(|e| Error::at(row, col, e))?);
+            }
+            height += 1;
+        }
+        Ok(Self {
+            data,
+            width: width.unwrap_or(0),
+            height,
+        })
     }
 
-    pub fn iter_cols(&self) -> impl Iterator<Item = Vec<&T>> {
-        (0..self.data[0].len())
-            .map(move |col| self.data.iter().filter_map(|row| row.get(col)).collect())
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        p.within(self.width, self.height)
     }
 
-    pub fn diags_left(&self) -> Vec<Vec<&T>> {
-        let row_len = self.data.len() as i32;
-        let col_len = self.data[0].len() as i32;
-        let mut diagonals = Vec::new();
+    fn offset(&self, p: Point) -> Option<usize> {
+        self.contains(p)
+            .then(|| p.y as usize * self.width + p.x as usize)
+    }
+
+    pub fn get(&self, p: Point) -> Option<&T> {
+        self.offset(p).map(|i| &self.data[i])
+    }
 
-        for d in 0..(row_len + col_len - 1) {
-            let mut start_row = if d < col_len { 0 } else { d - col_len + 1 };
-            let mut start_col = if d < col_len { d } else { col_len - 1 };
+    pub fn get_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.offset(p).map(|i| &mut self.data[i])
+    }
 
-            let mut diag = Vec::new();
-            while start_row < row_len && start_col >= 0 {
-                diag.push(&self.data[start_row as usize][start_col as usize]);
-                start_row += 1;
-                start_col -= 1;
+    /// Sets the value at `p`, false if it is outside of the grid
+    pub fn replace(&mut self, p: Point, value: T) -> bool {
+        match self.get_mut(p) {
+            Some(x) => {
+                *x = value;
+                true
             }
-            diagonals.push(diag);
+            None => false,
         }
-        diagonals
-    }
-    pub fn diags_right(&self) -> Vec<Vec<&T>> {
-        let row_len = self.data.len() as i32;
-        let col_len = self.data[0].len() as i32;
-        let mut diagonals = Vec::new();
-
-        for d in 0..(row_len + col_len - 1) {
-            println!("{d}");
-            let mut start_row = if d < col_len { row_len - 1 } else { 0 };
-            let mut start_col = if d < col_len { d } else { col_len - 1 };
-
-            let mut diag = Vec::new();
-            while start_row < row_len && start_col >= 0 && start_row >= 0 {
-                diag.push(&self.data[start_row as usize][start_col as usize]);
-                start_row -= 1;
-                start_col += 1;
-            }
-            diagonals.push(diag);
+    }
+
+    /// The rows from top to bottom
+    pub fn iter(&self) -> impl Iterator<Item = &[T]> {
+        // chunks panics on a size of 0, an empty grid has no rows anyway
+        self.data.chunks(self.width.max(1))
+    }
+
+    /// Every value with its position, row by row
+    pub fn indexed(&self) -> impl Iterator<Item = (Point, &T)> {
+        let width = self.width;
+        self.data
+            .iter()
+            .enumerate()
+            .map(move |(i, x)| (Point::new((i % width) as i32, (i / width) as i32), x))
+    }
+
+    /// The position of the first value, row by row, that matches `predicate`
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.indexed().find(|(_, x)| predicate(x)).map(|(p, _)| p)
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = Vec<&T>> {
+        self.iter().map(|r| r.iter().collect())
+    }
+
+    pub fn iter_cols(&self) -> impl Iterator<Item = Vec<&T>> {
+        (0..self.width).map(move |x| {
+            (0..self.height)
+                .map(|y| &self[Point::new(x as i32, y as i32)])
+                .collect()
+        })
+    }
+
+    /// Walks from `start`, included, in any of the eight directions until the edge of the grid
+    pub fn iter_from_index<'a>(
+        &'a self,
+        start: Point,
+        direction: &'a GridDirection,
+    ) -> GridIterator<'a, T> {
+        GridIterator {
+            grid: self,
+            pos: start,
+            step: Vector::from(*direction),
         }
-        diagonals
+    }
+
+    /// The neighbors of `p` in `directions` that are on the grid
+    fn neighbors_in(
+        &self,
+        p: Point,
+        directions: impl Iterator<Item = GridDirection>,
+    ) -> impl Iterator<Item = (Point, &T)> {
+        directions.filter_map(move |dir| {
+            let q = p + Vector::from(dir);
+            self.get(q).map(|x| (q, x))
+        })
+    }
+
+    /// The straight neighbors of `p` on the grid, clockwise starting at east
+    pub fn neighbors4(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors_in(p, GridDirection::iter())
+    }
+
+    /// The straight and diagonal neighbors of `p` on the grid, clockwise starting at east
+    pub fn neighbors8(&self, p: Point) -> impl Iterator<Item = (Point, &T)> {
+        self.neighbors_in(p, GridDirection::iter8())
+    }
+
+    /// Mutable references to the neighbors of `p` in `directions` that are on the grid, a
+    /// direction that is given twice is only yielded once
+    fn neighbors_in_mut(
+        &mut self,
+        p: Point,
+        directions: impl Iterator<Item = GridDirection>,
+    ) -> impl Iterator<Item = (Point, &mut T)> {
+        let found = directions
+            .filter_map(|dir| {
+                let q = p + Vector::from(dir);
+                self.offset(q).map(|i| (i, q))
+            })
+            .collect::<Vec<_>>();
+        let mut in_storage_order = (0..found.len()).collect::<Vec<_>>();
+        in_storage_order.sort_by_key(|&n| found[n].0);
+        in_storage_order.dedup_by_key(|n| found[*n].0);
+
+        // split every neighbor off the front of the rest of the storage, which only works in
+        // storage order, and then put them back in the order of the directions
+        let mut neighbors = Vec::with_capacity(in_storage_order.len());
+        let mut rest = self.data.as_mut_slice();
+        let mut start = 0;
+        for n in in_storage_order {
+            let (i, q) = found[n];
+            let (head, tail) = std::mem::take(&mut rest).split_at_mut(i + 1 - start);
+            neighbors.push((n, q, &mut head[i - start]));
+            rest = tail;
+            start = i + 1;
+        }
+        neighbors.sort_by_key(|&(n, _, _)| n);
+        neighbors.into_iter().map(|(_, q, x)| (q, x))
+    }
+
+    /// Like [`Grid::neighbors4`], but the values can be changed
+    pub fn neighbors4_mut(&mut self, p: Point) -> impl Iterator<Item = (Point, &mut T)> {
+        self.neighbors_in_mut(p, GridDirection::iter())
+    }
+
+    /// Like [`Grid::neighbors8`], but the values can be changed
+    pub fn neighbors8_mut(&mut self, p: Point) -> impl Iterator<Item = (Point, &mut T)> {
+        self.neighbors_in_mut(p, GridDirection::iter8())
+    }
+
+    pub fn has_neighbor(&self, p: Point, predicate: impl Fn(&T) -> bool) -> bool {
+        self.index_of_neighbor(p, predicate).is_some()
+    }
+
+    /// The position of the first straight neighbor, clockwise starting at east, that matches
+    /// `predicate`
+    pub fn index_of_neighbor(&self, p: Point, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.neighbors4(p)
+            .find(|(_, x)| predicate(x))
+            .map(|(q, _)| q)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &T {
+        self.get(p).unwrap_or_else(|| {
+            panic!(
+                "{p:?} is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
     }
 }
 
-pub enum DiagnonalDirection {
-    Start,
-    End,
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{p:?} is outside of the {width}x{height} grid"))
+    }
+}
+
+impl Grid<u8> {
+    /// A rectangle of single digits, like a height map
+    pub fn digits(text: &str) -> Result<Self, Error> {
+        Self::parse(text, |c| {
+            c.to_digit(10)
+                .map(|x| x as u8)
+                .ok_or_else(|| format!("invalid digit '{c}'"))
+        })
+    }
+}
+
+impl TryFrom<Vec<String>> for Grid<char> {
+    type Error = Error;
+
+    fn try_from(value: Vec<String>) -> Result<Self, Error> {
+        Self::parse(&value.join("\n"), Ok)
+    }
+}
+
+#[derive(Debug)]
+pub struct GridIterator<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Point,
+    step: Vector,
 }
 
-impl From<Vec<String>> for Grid<char> {
-    fn from(value: Vec<String>) -> Self {
-        let str = value
-            .into_iter()
-            .map(|x| x.chars().collect::<Vec<char>>())
-            .collect::<Vec<Vec<char>>>();
-        Self { data: str }
+impl<'a, T> Iterator for GridIterator<'a, T> {
+    type Item = (Point, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        let res = self.grid.get(self.pos).map(|x| (self.pos, x));
+        // once off the grid it stays off
+        if res.is_some() {
+            self.pos += self.step;
+        }
+        res
     }
 }
 
@@ -91,39 +291,173 @@ impl From<Vec<String>> for Grid<char> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[test]
-    fn create_from_vec() {
-        let g = Grid::from(vec![
+
+    fn new_grid() -> Grid<char> {
+        Grid::try_from(vec![
             "123".to_string(),
             "456".to_string(),
             "789".to_string(),
-        ]);
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn create_from_vec() {
+        let g = new_grid();
         assert!(g.iter_rows().next() == Some(vec![&'1', &'2', &'3']));
         assert!(g.iter_cols().next() == Some(vec![&'1', &'4', &'7']));
     }
 
     #[test]
-    fn iterate_diag_left() {
-        let g = Grid::from(vec![
-            "123".to_string(),
-            "456".to_string(),
-            "789".to_string(),
-        ]);
-        let mut it = g.diags_left().into_iter();
-        assert_eq!(it.next(), Some(vec![&'1']));
-        assert_eq!(it.next(), Some(vec![&'2', &'4']));
-        assert_eq!(it.next(), Some(vec![&'3', &'5', &'7']));
+    fn flat_storage() {
+        let mut g = Grid::try_from(vec!["123".to_string(), "456".to_string()]).unwrap();
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(
+            g.iter().collect::<Vec<_>>(),
+            [['1', '2', '3'], ['4', '5', '6']]
+        );
+        assert_eq!(g[Point::new(0, 1)], '4');
+        assert_eq!(g.get(Point::new(3, 0)), None);
+        assert_eq!(g.get(Point::new(0, 2)), None);
+        assert!(g.replace(Point::new(2, 1), 'x'));
+        assert!(!g.replace(Point::new(0, 2), 'x'));
+        assert_eq!(g.position(|&c| c == 'x'), Some(Point::new(2, 1)));
     }
+
     #[test]
-    fn iterate_diag_right() {
-        let g = Grid::from(vec![
-            "123".to_string(),
-            "456".to_string(),
-            "789".to_string(),
-        ]);
-        let mut it = g.diags_right().into_iter();
-        assert_eq!(it.next(), Some(vec![&'3']));
-        assert_eq!(it.next(), Some(vec![&'2', &'6']));
-        assert_eq!(it.next(), Some(vec![&'1', &'5', &'9']));
+    fn parse_chars() {
+        let g = Grid::parse("12\n34", |c| {
+            c.to_digit(10).ok_or("not a digit".to_string())
+        });
+        assert_eq!(g, Grid::new(vec![vec![1, 2], vec![3, 4]]));
+        let e = Grid::parse("12\n3x", |c| {
+            c.to_digit(10).ok_or("not a digit".to_string())
+        });
+        assert_eq!(e, Err(Error::at(1, 1, "not a digit")));
+        let e = Grid::parse("12\n3", Ok).unwrap_err();
+        assert_eq!(e, Error::at_line(1, "expected 2 columns, found 1"));
+        assert_eq!(
+            Grid::new(vec![vec![1, 2], vec![3]]),
+            Err(Error::at_line(1, "expected 2 columns, found 1"))
+        );
+        assert!(Grid::try_from(vec!["ab".to_string(), "abc".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parse_digits() {
+        assert_eq!(
+            Grid::digits("01\n23"),
+            Grid::new(vec![vec![0, 1], vec![2, 3]])
+        );
+        assert_eq!(
+            Grid::digits("01\n2x"),
+            Err(Error::at(1, 1, "invalid digit 'x'"))
+        );
+    }
+
+    #[test]
+    fn gird_has_neighbor() {
+        let g = new_grid();
+        assert!(g.has_neighbor(Point::new(0, 0), |x| *x == '2'));
+        assert!(g.has_neighbor(Point::new(0, 0), |x| *x == '4'));
+        assert!(g.has_neighbor(Point::new(1, 1), |x| *x == '2'));
+        assert!(g.has_neighbor(Point::new(1, 1), |x| *x == '4'));
+        assert!(g.has_neighbor(Point::new(1, 1), |x| *x == '6'));
+        assert!(g.has_neighbor(Point::new(1, 1), |x| *x == '8'));
+        assert!(!g.has_neighbor(Point::new(1, 1), |x| *x == '9'));
+        assert!(!g.has_neighbor(Point::new(0, 0), |x| *x == '9'));
+        assert_eq!(
+            g.index_of_neighbor(Point::new(1, 1), |x| *x == '8'),
+            Some(Point::new(1, 2))
+        );
+
+        let wanted = '6';
+        assert!(g.has_neighbor(Point::new(2, 2), |&x| x == wanted));
+    }
+
+    #[test]
+    fn neighbors() {
+        let g = new_grid();
+        let values =
+            |it: &mut dyn Iterator<Item = (Point, &char)>| it.map(|(_, &c)| c).collect::<String>();
+        assert_eq!(values(&mut g.neighbors4(Point::new(1, 1))), "6842");
+        assert_eq!(values(&mut g.neighbors8(Point::new(1, 1))), "69874123");
+        assert_eq!(values(&mut g.neighbors4(Point::new(0, 0))), "24");
+        assert_eq!(values(&mut g.neighbors8(Point::new(2, 0))), "652");
+        assert_eq!(
+            g.neighbors4(Point::new(0, 2)).next(),
+            Some((Point::new(1, 2), &'8'))
+        );
+        assert_eq!(g.neighbors8(Point::new(5, 5)).count(), 0);
+    }
+
+    #[test]
+    fn neighbors_mut() {
+        let mut g = Grid::new(vec![vec![0; 3]; 3]).unwrap();
+        for (i, (_, x)) in g.neighbors8_mut(Point::new(1, 1)).enumerate() {
+            *x = i + 1;
+        }
+        assert_eq!(
+            g.iter().collect::<Vec<_>>(),
+            [[6, 7, 8], [5, 0, 1], [4, 3, 2]]
+        );
+
+        for (_, x) in g.neighbors4_mut(Point::new(0, 0)) {
+            *x = 0;
+        }
+        assert_eq!(g.iter().next(), Some(&[6, 0, 8][..]));
+        let points = g
+            .neighbors4_mut(Point::new(2, 2))
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        assert_eq!(points, [Point::new(1, 2), Point::new(2, 1)]);
+
+        let twice = [
+            GridDirection::West,
+            GridDirection::North,
+            GridDirection::West,
+        ];
+        let points = g
+            .neighbors_in_mut(Point::new(1, 1), twice.into_iter())
+            .map(|(p, _)| p)
+            .collect::<Vec<_>>();
+        assert_eq!(points, [Point::new(0, 1), Point::new(1, 0)]);
+    }
+
+    #[test]
+    fn iter_row() {
+        let g = Grid::try_from(vec!["123".to_string(), "456".to_string()]).unwrap();
+
+        let mut it = g.iter_from_index(Point::new(0, 1), &GridDirection::East);
+
+        assert_eq!(it.next(), Some((Point::new(0, 1), &'4')));
+        assert_eq!(it.next(), Some((Point::new(1, 1), &'5')));
+        assert_eq!(it.next(), Some((Point::new(2, 1), &'6')));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn iter_col() {
+        let g = Grid::try_from(vec!["123".to_string(), "456".to_string()]).unwrap();
+
+        let mut it = g.iter_from_index(Point::new(1, 0), &GridDirection::South);
+
+        assert_eq!(it.next(), Some((Point::new(1, 0), &'2')));
+        assert_eq!(it.next(), Some((Point::new(1, 1), &'5')));
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn iter_diagonal() {
+        let g = new_grid();
+        let walk = |start, dir| {
+            g.iter_from_index(start, &dir)
+                .map(|(_, &c)| c)
+                .collect::<String>()
+        };
+        assert_eq!(walk(Point::new(0, 0), GridDirection::SouthEast), "159");
+        assert_eq!(walk(Point::new(0, 2), GridDirection::NorthEast), "753");
+        assert_eq!(walk(Point::new(2, 1), GridDirection::SouthWest), "68");
+        assert_eq!(walk(Point::new(1, 1), GridDirection::NorthWest), "51");
     }
 }
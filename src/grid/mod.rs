@@ -1,2 +1,8 @@
+pub mod direction;
+#[allow(clippy::module_inception)]
 pub mod grid;
-pub mod grid2;
+pub mod point;
+
+pub use direction::GridDirection;
+pub use grid::Grid;
+pub use point::{Point, Vector};
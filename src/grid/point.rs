@@ -0,0 +1,189 @@
+use std::{
+    cmp::Ordering,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::grid::GridDirection;
+
+/// A position in a 2D grid, `x` is the column and `y` the row, growing to the right and down
+///
+/// Points are ordered row by row, the same order a grid is read in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+/// The difference between two points, like the step between two antennas
+pub type Vector = Point;
+
+impl Point {
+    #[allow(dead_code)]
+    pub const ZERO: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Steps needed to get to `other` moving only straight
+    #[allow(dead_code)]
+    pub fn manhattan(&self, other: Point) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Steps needed to get to `other` when diagonal steps are allowed
+    #[allow(dead_code)]
+    pub fn chebyshev(&self, other: Point) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// The smallest vector in the same direction that still lands on whole positions, so
+    /// `(4, -6)` becomes `(2, -3)`
+    pub fn normalized(&self) -> Vector {
+        match gcd(self.x.unsigned_abs(), self.y.unsigned_abs()) {
+            0 => *self,
+            // the divisor is 2^31 for `(i32::MIN, 0)`, which doesn't fit in an i32
+            d => Point::new(
+                (i64::from(self.x) / i64::from(d)) as i32,
+                (i64::from(self.y) / i64::from(d)) as i32,
+            ),
+        }
+    }
+
+    /// Whether the point lies in a `width` by `height` rectangle starting at the origin
+    pub fn within(&self, width: usize, height: usize) -> bool {
+        usize::try_from(self.x).is_ok_and(|x| x < width)
+            && usize::try_from(self.y).is_ok_and(|y| y < height)
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Ord for Point {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i32) -> Vector {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// `(x, y)`
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+/// One step in the direction
+impl From<GridDirection> for Vector {
+    fn from(dir: GridDirection) -> Self {
+        let (drow, dcol) = dir.offset();
+        Self::new(dcol, drow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point::new(3, 4);
+        let b = Point::new(1, 7);
+        assert_eq!(a - b, Point::new(2, -3));
+        assert_eq!(b + (a - b), a);
+        assert_eq!((a - b) * 2, Point::new(4, -6));
+        assert_eq!(-(a - b), b - a);
+
+        let mut c = a;
+        c += Vector::from(GridDirection::NorthEast);
+        assert_eq!(c, Point::new(4, 3));
+        c -= Point::new(4, 3);
+        assert_eq!(c, Point::ZERO);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(-2, 5);
+        let b = Point::new(1, 1);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(Point::new(4, -6).normalized(), Point::new(2, -3));
+        assert_eq!(Point::new(0, -5).normalized(), Point::new(0, -1));
+        assert_eq!(Point::ZERO.normalized(), Point::ZERO);
+        assert_eq!(Point::new(i32::MIN, 0).normalized(), Point::new(-1, 0));
+        assert_eq!(
+            Point::new(i32::MIN, i32::MIN).normalized(),
+            Point::new(-1, -1)
+        );
+    }
+
+    #[test]
+    fn bounds() {
+        assert!(Point::new(0, 2).within(1, 3));
+        assert!(!Point::new(1, 2).within(1, 3));
+        assert!(!Point::new(0, -1).within(1, 3));
+    }
+
+    #[test]
+    fn row_by_row_order() {
+        let a = Point::new(10, 1);
+        let b = Point::new(1, 10);
+        assert!(a < b);
+        assert!(Point::new(1, 1) < Point::new(2, 1));
+    }
+}
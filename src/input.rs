@@ -0,0 +1,387 @@
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// A line of input, or the value part of one, with its zero based line number and the column
+/// it starts at for errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub nr: usize,
+    pub col: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, message: impl Into<String>) -> Error {
+        Error::at_line(self.nr, message)
+    }
+
+    /// The trimmed value of a `label: value` line
+    pub fn field(&self, label: &str) -> Result<Line<'a>, Error> {
+        match self.text.split_once(':') {
+            Some((l, value)) if l.trim() == label => Ok(Line {
+                nr: self.nr,
+                col: self.col + self.text.len() - value.trim_start().len(),
+                text: value.trim(),
+            }),
+            _ => Err(self.error(format!("expected '{label}: <value>'"))),
+        }
+    }
+
+    pub fn parse<T: FromStr>(&self) -> Result<T, Error> {
+        self.text
+            .parse()
+            .map_err(|_| Error::at(self.nr, self.col, format!("invalid value '{}'", self.text)))
+    }
+
+    /// The values of the line separated by commas, whitespace or both, anything else is an error
+    pub fn list<T: FromStr>(&self) -> Result<Vec<T>, Error> {
+        list(self.nr, self.col, self.text)
+    }
+
+    /// The values of the line separated by exactly `separator`, so `75,47` parses but `75, 47`
+    /// does not
+    pub fn split<T: FromStr>(&self, separator: char) -> Result<Vec<T>, Error> {
+        parse_each(self.nr, self.col, self.text, self.text.split(separator))
+    }
+
+    /// Exactly `N` values like [`Line::list`], for destructuring lines like `3   4` into `[a, b]`.
+    /// An array instead of a tuple works for any `N` and destructures the same way.
+    pub fn list_n<T: FromStr, const N: usize>(&self) -> Result<[T; N], Error> {
+        let values = self.list()?;
+        let found = values.len();
+        values
+            .try_into()
+            .map_err(|_| self.error(format!("expected {N} values, found {found}")))
+    }
+
+    /// Every integer in the line, in order, ignoring the text around them. Only meant for lines
+    /// like `p=0,4 v=3,-3` that mix numbers and text, use [`Line::list`] where the line should
+    /// hold nothing else.
+    ///
+    /// A `-` directly in front of a number makes it negative, unless it follows a digit like in
+    /// `3-4`. Numbers that don't fit in `T` are an error, so are negative numbers for unsigned
+    /// types.
+    // no day has such lines yet: they hold only values, read with `list`, or label them, read
+    // with `field` and `fields`, which also check the text around the numbers
+    #[allow(dead_code)]
+    pub fn ints<T: FromStr>(&self) -> Result<Vec<T>, Error> {
+        let bytes = self.text.as_bytes();
+        let mut numbers = vec![];
+        let mut i = 0;
+        while i < bytes.len() {
+            let start = i;
+            let sign = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_digit());
+            if !sign && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            i += 1;
+            while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+                i += 1;
+            }
+            let x = &self.text[start..i];
+            let col = self.col + start;
+            numbers.push(
+                x.parse()
+                    .map_err(|_| Error::at(self.nr, col, format!("invalid number '{x}'")))?,
+            );
+        }
+        Ok(numbers)
+    }
+
+    /// Matches the line against a template like `{}: {list}`, where the text outside of braces
+    /// must appear as is and every `{...}` captures the text up to the next literal part. The
+    /// name inside the braces only documents the field, how it is parsed is up to [`Fields`].
+    ///
+    /// Templates with two placeholders without text in between or an unclosed brace can't be
+    /// matched unambiguously and are an error too.
+    pub fn fields(&self, template: &str) -> Result<Fields<'a>, Error> {
+        let col = |rest: &str| self.col + self.text.len() - rest.len();
+        let invalid = |reason| self.error(format!("invalid template '{template}': {reason}"));
+        let mut captures = vec![];
+        let mut rest = self.text;
+        let mut remaining = template;
+        let mut placeholder = false;
+        loop {
+            let (literal, next) = match remaining.find('{') {
+                Some(i) => (&remaining[..i], Some(&remaining[i..])),
+                None => (remaining, None),
+            };
+
+            if !placeholder {
+                rest = rest.strip_prefix(literal).ok_or_else(|| {
+                    Error::at(self.nr, col(rest), format!("expected '{literal}'"))
+                })?;
+            } else if literal.is_empty() {
+                if next.is_some() {
+                    return Err(invalid("placeholders must be separated by text"));
+                }
+                captures.push((col(rest), rest));
+                rest = "";
+            } else {
+                // the field runs up to the literal, without it the line ends too early
+                let i = rest
+                    .find(literal)
+                    .ok_or_else(|| Error::at(self.nr, col(""), format!("expected '{literal}'")))?;
+                captures.push((col(rest), &rest[..i]));
+                rest = &rest[i + literal.len()..];
+            }
+
+            let Some(next) = next else {
+                break;
+            };
+            let end = next
+                .find('}')
+                .ok_or_else(|| invalid("unclosed placeholder"))?;
+            remaining = &next[end + 1..];
+            placeholder = true;
+        }
+
+        if !rest.is_empty() {
+            return Err(Error::at(
+                self.nr,
+                col(rest),
+                format!("unexpected '{rest}'"),
+            ));
+        }
+        Ok(Fields {
+            nr: self.nr,
+            captures: captures.into_iter(),
+        })
+    }
+}
+
+/// The captured fields of a line matched by [`Line::fields`], parsed in template order
+#[derive(Debug)]
+pub struct Fields<'a> {
+    nr: usize,
+    /// Column and text of every field
+    captures: std::vec::IntoIter<(usize, &'a str)>,
+}
+
+impl Fields<'_> {
+    fn next_field(&mut self) -> Result<(usize, &str), Error> {
+        self.captures
+            .next()
+            .ok_or_else(|| Error::at_line(self.nr, "more fields parsed than the template has"))
+    }
+
+    /// Parses the next field as a single value, surrounding whitespace is ignored
+    pub fn value<T: FromStr>(&mut self) -> Result<T, Error> {
+        let nr = self.nr;
+        let (col, text) = self.next_field()?;
+        let start = col + text.len() - text.trim_start().len();
+        text.trim()
+            .parse()
+            .map_err(|_| Error::at(nr, start, format!("invalid value '{}'", text.trim())))
+    }
+
+    /// Parses the next field as values separated by commas, whitespace or both
+    pub fn list<T: FromStr>(&mut self) -> Result<Vec<T>, Error> {
+        let nr = self.nr;
+        let (col, text) = self.next_field()?;
+        list(nr, col, text)
+    }
+}
+
+/// Values separated by commas, whitespace or both in `text`, which starts at `col` of line `nr`
+fn list<T: FromStr>(nr: usize, col: usize, text: &str) -> Result<Vec<T>, Error> {
+    let values = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|x| !x.is_empty());
+    parse_each(nr, col, text, values)
+}
+
+/// Parses each of `values`, which are slices of `text`, reporting errors at their own column
+fn parse_each<'a, T: FromStr>(
+    nr: usize,
+    col: usize,
+    text: &'a str,
+    values: impl Iterator<Item = &'a str>,
+) -> Result<Vec<T>, Error> {
+    values
+        .map(|x| {
+            let start = col + (x.as_ptr() as usize - text.as_ptr() as usize);
+            x.parse()
+                .map_err(|_| Error::at(nr, start, format!("invalid value '{x}'")))
+        })
+        .collect()
+}
+
+/// Every line of `text` with its line number
+pub fn lines(text: &str) -> impl Iterator<Item = Line<'_>> {
+    text.lines()
+        .enumerate()
+        .map(|(nr, text)| Line { nr, col: 0, text })
+}
+
+/// A block of consecutive non-empty lines, sections are separated by blank lines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    lines: Vec<Line<'a>>,
+}
+
+impl<'a> Section<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> + '_ {
+        self.lines.iter().copied()
+    }
+
+    /// The value of the `label: value` line with the given label
+    pub fn field(&self, label: &str) -> Result<Line<'a>, Error> {
+        self.lines()
+            .find_map(|x| x.field(label).ok())
+            .ok_or_else(|| {
+                let nr = self.lines.first().map_or(0, |x| x.nr);
+                Error::at_line(nr, format!("missing '{label}: <value>'"))
+            })
+    }
+}
+
+/// Splits text into sections on blank lines, runs of blank lines count as one separator
+pub fn sections(text: &str) -> Vec<Section<'_>> {
+    let mut sections = vec![];
+    let mut current = vec![];
+    for line in lines(text) {
+        if line.text.trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(Section {
+                    lines: std::mem::take(&mut current),
+                });
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        sections.push(Section { lines: current });
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(nr: usize, text: &str) -> Line<'_> {
+        Line { nr, col: 0, text }
+    }
+
+    #[test]
+    fn split_sections() {
+        let s = sections("\na\nb\n\n\nc\n");
+        assert_eq!(s.len(), 2);
+        assert_eq!(s[0].lines().map(|x| x.text).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(s[1].lines().next(), Some(line(5, "c")));
+    }
+
+    #[test]
+    fn fields_and_lists() {
+        let s = &sections("Register A: 729\nProgram: 0,1, 5 4")[0];
+        assert_eq!(
+            s.field("Register A").and_then(|x| x.parse::<u32>()),
+            Ok(729)
+        );
+        assert_eq!(
+            s.field("Program").and_then(|x| x.list::<u8>()),
+            Ok(vec![0, 1, 5, 4])
+        );
+        assert_eq!(s.field("Register B").unwrap_err().line, Some(0));
+        // columns count from the start of the whole line
+        let program = sections("Program:  0,x")[0].field("Program").unwrap();
+        assert_eq!(
+            program.list::<u8>().unwrap_err(),
+            Error::at(0, 12, "invalid value 'x'")
+        );
+
+        let line = line(3, "1,-2");
+        assert_eq!(
+            line.list::<u32>().unwrap_err(),
+            Error::at(3, 2, "invalid value '-2'")
+        );
+        assert!(line.field("Register A").is_err());
+    }
+
+    #[test]
+    fn lists_are_strict() {
+        assert_eq!(line(0, "7 6 4 2 1").list::<i32>(), Ok(vec![7, 6, 4, 2, 1]));
+        assert_eq!(line(0, "").list::<i32>(), Ok(vec![]));
+        assert_eq!(
+            line(1, "7 6 x4").list::<i32>().unwrap_err(),
+            Error::at(1, 4, "invalid value 'x4'")
+        );
+        assert_eq!(line(0, "3   4").list_n::<i32, 2>(), Ok([3, 4]));
+        assert_eq!(
+            line(2, "1 2 3").list_n::<i32, 2>().unwrap_err(),
+            Error::at_line(2, "expected 2 values, found 3")
+        );
+        assert!(line(0, "3   4 apples").list_n::<i32, 2>().is_err());
+        assert_eq!(line(0, "75,47,61").split::<i32>(','), Ok(vec![75, 47, 61]));
+        assert_eq!(
+            line(3, "75, 47").split::<i32>(',').unwrap_err(),
+            Error::at(3, 3, "invalid value ' 47'")
+        );
+    }
+
+    #[test]
+    fn ints_ignore_text() {
+        assert_eq!(line(0, "p=0,4 v=3,-3").ints::<i64>(), Ok(vec![0, 4, 3, -3]));
+        assert_eq!(line(0, "3-4: x").ints::<u32>(), Ok(vec![3, 4]));
+        assert_eq!(line(0, "none").ints::<u32>(), Ok(vec![]));
+        assert_eq!(
+            line(4, "a=1 b=256").ints::<u8>().unwrap_err(),
+            Error::at(4, 6, "invalid number '256'")
+        );
+    }
+
+    #[test]
+    fn template_fields() {
+        let mut f = line(2, "3267: 81 40 27").fields("{}: {list}").unwrap();
+        assert_eq!(f.value::<u64>(), Ok(3267));
+        assert_eq!(f.list::<u64>(), Ok(vec![81, 40, 27]));
+
+        let mut f = line(0, "47|53").fields("{}|{}").unwrap();
+        assert_eq!((f.value::<u32>(), f.value::<u32>()), (Ok(47), Ok(53)));
+
+        let mut f = line(0, "p=0,4 v=3,-3").fields("p={},{} v={list}").unwrap();
+        assert_eq!(f.value::<i32>(), Ok(0));
+        assert_eq!(f.value::<i32>(), Ok(4));
+        assert_eq!(f.list::<i32>(), Ok(vec![3, -3]));
+    }
+
+    #[test]
+    fn template_errors() {
+        let l = line(1, "3267 81 40 27");
+        assert_eq!(
+            l.fields("{}:{list}").unwrap_err(),
+            Error::at(1, 13, "expected ':'")
+        );
+        assert!(l.fields("{}{}").is_err());
+        assert!(l.fields("{} {list").is_err());
+        let mut f = l.fields("{}").unwrap();
+        assert!(f.value::<String>().is_ok());
+        assert_eq!(
+            f.value::<u32>().unwrap_err(),
+            Error::at_line(1, "more fields parsed than the template has")
+        );
+
+        let mut f = line(1, "10: 5 x").fields("{}: {list}").unwrap();
+        assert_eq!(f.value::<u32>(), Ok(10));
+        assert_eq!(
+            f.list::<u32>().unwrap_err(),
+            Error::at(1, 6, "invalid value 'x'")
+        );
+
+        let l = line(0, "a: 1 b");
+        assert_eq!(
+            l.fields("a: {}")
+                .and_then(|mut f| f.value::<u8>())
+                .unwrap_err()
+                .col,
+            Some(3)
+        );
+        assert_eq!(l.fields("b: {}").unwrap_err().col, Some(0));
+    }
+}
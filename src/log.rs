@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How much detail to print, each level includes the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info = 1,
+    Debug = 2,
+    Trace = 3,
+}
+
+/// Nothing is logged until the verbosity is raised with `-v`
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+
+/// Sets how many `-v` flags were given, `-v` logs info, `-vv` debug and `-vvv` trace messages
+pub fn set_verbosity(verbosity: u8) {
+    VERBOSITY.store(verbosity, Ordering::Relaxed);
+}
+
+impl Level {
+    /// Whether messages of this level are printed with `verbosity` `-v` flags
+    pub fn shown_at(self, verbosity: u8) -> bool {
+        verbosity >= self as u8
+    }
+}
+
+pub fn enabled(level: Level) -> bool {
+    level.shown_at(VERBOSITY.load(Ordering::Relaxed))
+}
+
+/// Logs to stderr, so answers on stdout stay readable
+macro_rules! log {
+    ($level: expr, $($arg: tt)*) => {
+        if $crate::log::enabled($level) {
+            eprintln!("[{}] {}", format!("{:?}", $level).to_lowercase(), format_args!($($arg)*));
+        }
+    };
+}
+
+macro_rules! info {
+    ($($arg: tt)*) => { $crate::log::log!($crate::log::Level::Info, $($arg)*) };
+}
+
+macro_rules! debug {
+    ($($arg: tt)*) => { $crate::log::log!($crate::log::Level::Debug, $($arg)*) };
+}
+
+macro_rules! trace {
+    ($($arg: tt)*) => { $crate::log::log!($crate::log::Level::Trace, $($arg)*) };
+}
+
+pub(crate) use {debug, info, log, trace};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert!(Level::Info.shown_at(2) && Level::Debug.shown_at(2));
+        assert!(!Level::Trace.shown_at(2));
+        assert!(!Level::Info.shown_at(0));
+    }
+}
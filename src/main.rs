@@ -1,16 +1,25 @@
-#![allow(unused_imports)]
-use day11::Day11;
-use day17::Day17;
-use day2::Day2;
-use day3::Day3;
-use day5::Day5;
-use day6::Day6;
-use day7::Day7;
-use day8::Day8;
-
-use day1::Day1;
-use std::{fmt::Display, fs};
+use answers::{Answers, Status};
+use bench::{Change, History};
+use cache::Cache;
+use cli::{BenchArgs, Command, Days, Format, NewDayArgs, ReportArgs, RunArgs};
+use error::Error;
+use registry::DayEntry;
 
+use std::{
+    fmt::Display,
+    fs,
+    io::{BufRead, Read},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    str::FromStr,
+    sync::{Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+mod answers;
+mod bench;
+mod cache;
+mod cli;
 mod day1;
 mod day11;
 mod day17;
@@ -21,29 +30,532 @@ mod day5;
 mod day6;
 mod day7;
 mod day8;
+mod error;
 mod graph;
 mod grid;
-fn main() {
-    // let data = ProblemData::from_string("1 2 3 4 5".to_string());
-    let data = ProblemData::from_file(8, Case::Real);
-    let mut d = Day8::new(data);
+mod input;
+mod log;
+mod pool;
+mod registry;
+mod report;
+mod scaffold;
+
+fn main() -> ExitCode {
+    let (verbosity, args) = cli::verbosity(std::env::args().skip(1));
+    log::set_verbosity(verbosity);
+    let command = match cli::parse(args.into_iter()) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::Run(args) => run(args),
+        Command::Bench(args) => bench(args),
+        Command::Report(args) => report(args),
+        Command::List => {
+            let dir = ProblemData::input_dir();
+            for entry in registry::all() {
+                let examples = ProblemData::examples(&dir, entry.day);
+                if examples.is_empty() {
+                    println!("day {:>2}: {}", entry.day, entry.title);
+                } else {
+                    let examples = examples.join(", ");
+                    println!(
+                        "day {:>2}: {} (examples: {examples})",
+                        entry.day, entry.title
+                    );
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Command::NewDay(args) => new_day(args),
+    }
+}
+
+fn report(args: ReportArgs) -> ExitCode {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+    let dir = args.input_dir.unwrap_or_else(ProblemData::input_dir);
+    let rows = match report::collect(registry::all(), &dir, &args.case, &parts, args.timeout) {
+        Ok(rows) => rows,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match args.format {
+        Format::Table => println!("{}", report::table(&rows)),
+        Format::Json => println!("{}", report::json(&rows)),
+        Format::Csv => print!("{}", report::csv(&rows)),
+    }
+    if rows.iter().all(|x| x.is_ok()) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn new_day(args: NewDayArgs) -> ExitCode {
+    let NewDayArgs {
+        day,
+        title,
+        input_dir,
+    } = args;
+    // the sources are edited in the checkout the binary was built from and the inputs go next to
+    // them, unless another directory is asked for
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let data_dir = input_dir
+        .or_else(|| std::env::var_os("AOC_INPUT_DIR").map(PathBuf::from))
+        .unwrap_or_else(|| root.join("data"));
+    match scaffold::new_day(root, &data_dir, day, &title) {
+        Ok(written) => {
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: RunArgs) -> ExitCode {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+    let input = args
+        .input
+        .unwrap_or_else(|| Input::Dir(ProblemData::input_dir()));
+    let answers = match &input {
+        Input::Dir(dir) => Answers::load(dir),
+        Input::File(_) | Input::Stdin => Ok(Answers::default()),
+    };
+    let answers = match answers {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // without --cached the input directory is left alone
+    let cache = match &input {
+        Input::Dir(dir) if args.cached => Some(Mutex::new(Cache::load(dir).unwrap_or_else(|e| {
+            eprintln!("warning: ignoring the answer cache: {e}");
+            Cache::default()
+        }))),
+        _ => None,
+    };
+
+    let (entries, indent) = match args.days {
+        Days::One(day) => match registry::get(day) {
+            Some(entry) => (vec![entry], ""),
+            None => {
+                eprintln!("error: day {day} is not implemented");
+                return ExitCode::FAILURE;
+            }
+        },
+        Days::All => (registry::all().collect::<Vec<_>>(), "  "),
+    };
+    // a day without examples is only an error when it was asked for by itself
+    let entries = match &input {
+        Input::Dir(dir) if args.examples && args.days == Days::All => entries
+            .into_iter()
+            .filter(|entry| !ProblemData::examples(dir, entry.day).is_empty())
+            .collect(),
+        _ => entries,
+    };
+    let header = |entry: &DayEntry| format!("day {}: {}", entry.day, entry.title);
+    // with a single job days run one after another and can print as they go
+    let live = args.jobs <= 1;
+
+    let run_one = |&entry: &&DayEntry| {
+        let mut out = Output::new(live);
+        if args.days == Days::All {
+            out.line(header(entry));
+        }
+        out.indent = indent.to_string();
+        let options = RunOptions {
+            parts: &parts,
+            answers: None,
+            cache: None,
+            timeout: args.timeout,
+        };
+        let ok = match &input {
+            Input::Dir(dir) => {
+                let options = RunOptions {
+                    answers: Some(&answers),
+                    cache: cache.as_ref(),
+                    ..options
+                };
+                if args.examples {
+                    run_examples(entry, dir, &options, &mut out)
+                } else {
+                    run_day(entry, &input, &args.case, &options, &mut out)
+                }
+            }
+            Input::File(_) | Input::Stdin => run_day(entry, &input, &args.case, &options, &mut out),
+        };
+        (out, ok)
+    };
+
+    let mut ok = true;
+    pool::run_ordered(&entries, args.jobs, run_one, |entry, result| match result {
+        Ok((out, day_ok)) => {
+            out.print();
+            ok &= day_ok;
+        }
+        Err(message) => {
+            if !live && args.days == Days::All {
+                println!("{}", header(entry));
+            }
+            eprintln!("{indent}error: day {} panicked: {message}", entry.day);
+            ok = false;
+        }
+    });
+
+    if let (Input::Dir(dir), Some(cache)) = (&input, cache) {
+        let cache = cache.into_inner().unwrap_or_else(|e| e.into_inner());
+        if let Err(e) = cache.save(dir) {
+            eprintln!("warning: could not save the answer cache: {e}");
+        }
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Lines printed while running a day, either printed right away or kept until [`Output::print`]
+/// so days solved in parallel don't mix their output
+struct Output {
+    live: bool,
+    /// Put in front of every line
+    indent: String,
+    /// Buffered lines, and whether they go to stderr
+    lines: Vec<(bool, String)>,
+}
+
+impl Output {
+    fn new(live: bool) -> Self {
+        Self {
+            live,
+            indent: String::new(),
+            lines: vec![],
+        }
+    }
 
-    {
-        let res1 = d.solve1();
-        println!("part1: {}", res1.into());
+    fn push(&mut self, error: bool, text: &str) {
+        for line in text.lines() {
+            let line = format!("{}{line}", self.indent);
+            match (self.live, error) {
+                (true, false) => println!("{line}"),
+                (true, true) => eprintln!("{line}"),
+                (false, _) => self.lines.push((error, line)),
+            }
+        }
+    }
+
+    fn line(&mut self, text: impl AsRef<str>) {
+        self.push(false, text.as_ref());
+    }
+
+    fn error(&mut self, text: impl AsRef<str>) {
+        self.push(true, text.as_ref());
+    }
+
+    fn print(&self) {
+        for (error, line) in &self.lines {
+            if *error {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        }
+    }
+}
+
+fn bench(args: BenchArgs) -> ExitCode {
+    let BenchArgs {
+        run: args,
+        runs,
+        save,
+        compare,
+        threshold,
+        history: history_path,
+    } = args;
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![Part::One, Part::Two],
+    };
+    let input = args
+        .input
+        .unwrap_or_else(|| Input::Dir(ProblemData::input_dir()));
+    let entries = match args.days {
+        Days::One(day) => match registry::get(day) {
+            Some(entry) => vec![entry],
+            None => {
+                eprintln!("error: day {day} is not implemented");
+                return ExitCode::FAILURE;
+            }
+        },
+        Days::All => registry::all().collect(),
+    };
+    let mut history = match History::load(&history_path) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if let Some(label) = compare.as_ref().filter(|x| !history.has_label(x)) {
+        eprintln!(
+            "error: nothing saved as '{label}' in {}",
+            history_path.display()
+        );
+        return ExitCode::FAILURE;
+    }
+
+    let mut ok = true;
+    for entry in entries {
+        println!("day {}: {} ({runs} runs)", entry.day, entry.title);
+        let timings = ProblemData::from_input(&input, entry.day, &args.case)
+            .and_then(|data| bench::bench_day(entry, &data, &parts, runs));
+        let timings = match timings {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("  error: {e}");
+                ok = false;
+                continue;
+            }
+        };
+
+        for (step, stats) in timings.steps() {
+            let baseline = compare
+                .as_ref()
+                .and_then(|label| history.get(label, entry.day, &args.case, &step));
+            match baseline {
+                Some(baseline) => {
+                    let change = Change::new(baseline, &stats, f64::from(threshold));
+                    ok &= !matches!(change, Change::Slower(_));
+                    println!("  {step:<5}  {stats}  {change}");
+                }
+                None => match &compare {
+                    Some(label) => println!("  {step:<5}  {stats}  nothing saved as '{label}'"),
+                    None => println!("  {step:<5}  {stats}"),
+                },
+            }
+        }
+        if let Some(label) = &save {
+            history.record(label, entry.day, &args.case, &timings);
+        }
+    }
+
+    if save.is_some() {
+        if let Err(e) = history.save(&history_path) {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// How the days of a run are solved and checked
+struct RunOptions<'a> {
+    parts: &'a [Part],
+    /// Known answers to check against
+    answers: Option<&'a Answers>,
+    /// New answers are stored here, and taken from it instead of solving with `--cached`
+    cache: Option<&'a Mutex<Cache>>,
+    /// How long solving a part may take
+    timeout: Option<Duration>,
+}
+
+/// Solves the given parts of a day and prints the answers. A part that panics or takes longer
+/// than the timeout is reported and the remaining parts are solved with a freshly parsed solver.
+/// Returns false if the input could not be read or parsed, or a part failed
+fn run_day(
+    entry: &DayEntry,
+    input: &Input,
+    case: &Case,
+    options: &RunOptions,
+    out: &mut Output,
+) -> bool {
+    let data = match ProblemData::from_input(input, entry.day, case) {
+        Ok(data) => data,
+        Err(e) => {
+            out.error(format!("error: {e}"));
+            return false;
+        }
+    };
+    let checksum = cache::checksum(data.as_str());
+    // only parsed once a part is not cached
+    let mut solver = None;
+    let RunOptions {
+        parts,
+        answers,
+        cache,
+        timeout,
+    } = *options;
+    fn lock(cache: &Mutex<Cache>) -> MutexGuard<'_, Cache> {
+        // a panicking day can't leave the cache half updated, so poisoning is ignored
+        cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // solvers may keep state from one part for the next, so cached answers are only used when
+    // every part is cached and otherwise all parts are solved
+    let cached = cache.and_then(|x| {
+        let cache = lock(x);
+        parts
+            .iter()
+            .map(|&part| cache.get(entry.day, part, case, checksum).cloned())
+            .collect::<Option<Vec<_>>>()
+    });
+
+    let mut ok = true;
+    for (i, &part) in parts.iter().enumerate() {
+        let (answer, note) = match cached.as_ref().map(|x| x[i].clone()) {
+            Some(answer) => (answer, "  (cached)"),
+            None => {
+                let current = match solver.take() {
+                    Some(s) => s,
+                    None => match (entry.new)(data.clone()) {
+                        Ok(s) => s,
+                        Err(e) => {
+                            out.error(format!("error: {e}"));
+                            return false;
+                        }
+                    },
+                };
+                let start = Instant::now();
+                match pool::run_isolated(current, timeout, move |s| s.solve(part)) {
+                    Ok((s, answer)) => {
+                        log::info!(
+                            "day {} part {part} solved in {:.2?}",
+                            entry.day,
+                            start.elapsed()
+                        );
+                        solver = Some(s);
+                        if let Some(cache) = cache {
+                            lock(cache).insert(entry.day, part, case, checksum, &answer);
+                        }
+                        (answer, "")
+                    }
+                    Err(failure) => {
+                        out.line(format!("part{part}: {failure}"));
+                        ok = false;
+                        continue;
+                    }
+                }
+            }
+        };
+        let text = match &answer {
+            Answer::Grid(rows) => rows.lines().map(|x| format!("\n  {x}")).collect::<String>(),
+            _ => format!(" {answer}"),
+        };
+        match answers.map(|a| a.check(entry.day, part, case, &answer)) {
+            Some(Status::Unsolved) | None => out.line(format!("part{part}:{text}{note}")),
+            Some(status) => {
+                ok &= !matches!(status, Status::Fail(_));
+                out.line(format!("part{part}:{text}  {status}{note}"));
+            }
+        }
     }
-    {
-        let res2 = d.solve2();
-        println!("part2: {}", res2.into());
+    ok
+}
+
+/// Runs every example of a day found in the input directory
+fn run_examples(entry: &DayEntry, dir: &Path, options: &RunOptions, out: &mut Output) -> bool {
+    let examples = ProblemData::examples(dir, entry.day);
+    if examples.is_empty() {
+        out.error(format!("error: no examples for day {}", entry.day));
+        return false;
     }
+
+    let mut ok = true;
+    let indent = out.indent.len();
+    for name in examples {
+        out.line(format!("example {name}:"));
+        out.indent.push_str("  ");
+        let case = Case::Example(name);
+        let input = Input::Dir(dir.to_path_buf());
+        ok &= run_day(entry, &input, &case, options, out);
+        out.indent.truncate(indent);
+    }
+    ok
 }
 
-trait Solver {
-    fn new(data: ProblemData) -> Self;
+/// Days are solved on worker threads when running in parallel, so solvers must be `Send`
+trait Solver: Send {
+    /// Parses the input, returning an error pointing at the offending line instead of panicking
+    fn try_new(data: ProblemData) -> Result<Self, Error>
+    where
+        Self: Sized;
+
     fn solve1(&mut self) -> impl Into<Answer>;
     fn solve2(&mut self) -> impl Into<Answer>;
 }
 
+/// Object safe counterpart of [`Solver`], implemented for every solver so days can be boxed
+/// and picked at runtime
+trait DynSolver: Send {
+    fn solve(&mut self, part: Part) -> Answer;
+}
+
+impl<S: Solver> DynSolver for S {
+    fn solve(&mut self, part: Part) -> Answer {
+        match part {
+            Part::One => self.solve1().into(),
+            Part::Two => self.solve2().into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            x => Err(format!("invalid part '{x}', expected 1 or 2")),
+        }
+    }
+}
+
 // struct Day1 {
 //     data: ProblemData,
 // }
@@ -66,181 +578,307 @@ trait Solver {
 //     }
 // }
 
-#[allow(dead_code)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum Case {
     Test,
     Real,
+    /// One of several named examples, stored in `dayN/example_<name>.txt`
+    Example(String),
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Case::Test => write!(f, "test"),
+            Case::Real => write!(f, "real"),
+            Case::Example(name) => write!(f, "example:{name}"),
+        }
+    }
+}
+
+impl FromStr for Case {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            // the name becomes part of a file name, it must not lead out of the day's directory
+            Some(("example", name)) if name.contains(['/', '\\']) || name.contains("..") => {
+                Err(format!("invalid example name '{name}'"))
+            }
+            Some(("example", name)) if !name.is_empty() => Ok(Case::Example(name.to_string())),
+            _ => match s {
+                "test" => Ok(Case::Test),
+                "real" => Ok(Case::Real),
+                x => Err(format!(
+                    "invalid case '{x}', expected real, test or example:<name>"
+                )),
+            },
+        }
+    }
+}
+
+/// Where to read puzzle input from
+#[derive(Debug, PartialEq, Eq)]
+enum Input {
+    /// The day's file for the chosen case inside a directory
+    Dir(PathBuf),
+    /// A single file, regardless of day and case
+    File(PathBuf),
+    Stdin,
 }
 
+/// The raw text of a puzzle input
+///
+/// The input is kept as it was read so parsers can borrow from it, as lines, bytes or through a
+/// reader, without copying it first.
+#[derive(Clone)]
 struct ProblemData {
-    data: Vec<String>,
+    text: String,
+    /// The file the data was read from, used to point errors at it
+    source: Option<String>,
 }
 
+#[allow(dead_code)]
 impl ProblemData {
-    fn iter(&self) -> impl Iterator<Item = &String> {
-        self.data.iter()
+    fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.text.as_bytes()
+    }
+
+    /// Lines without their line endings, borrowed from the input
+    fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /// Lines with their line numbers, to point parse errors at them
+    fn numbered_lines(&self) -> impl Iterator<Item = input::Line<'_>> {
+        input::lines(&self.text)
+    }
+
+    /// Blocks of lines separated by blank lines, like the rules and updates of day 5
+    fn sections(&self) -> Vec<input::Section<'_>> {
+        input::sections(&self.text)
+    }
+
+    /// A buffered reader over the input, for parsers that work on a stream
+    fn reader(&self) -> impl BufRead + '_ {
+        self.text.as_bytes()
     }
 }
 
 #[allow(dead_code)]
 impl ProblemData {
-    fn from_file(day: u32, case: Case) -> Self {
-        let filename = match case {
-            Case::Test => format!("data/day{}_test.txt", day),
-            Case::Real => format!("data/day{}.txt", day),
-        };
+    /// The directory inputs are read from, `$AOC_INPUT_DIR` if set, otherwise `data`
+    fn input_dir() -> PathBuf {
+        std::env::var_os("AOC_INPUT_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("data"))
+    }
+
+    fn path(dir: &Path, day: u32, case: &Case) -> PathBuf {
+        match case {
+            Case::Test => dir.join(format!("day{}_test.txt", day)),
+            Case::Real => dir.join(format!("day{}.txt", day)),
+            Case::Example(name) => dir
+                .join(format!("day{}", day))
+                .join(format!("example_{}.txt", name)),
+        }
+    }
 
-        //read from file
-        let content = fs::read_to_string(filename)
-            .expect("File not found")
-            .lines()
-            .map(|x| x.to_string())
+    /// Names of the examples of a day, in natural order so `example_10` comes after `example_9`
+    fn examples(dir: &Path, day: u32) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(dir.join(format!("day{}", day))) else {
+            return vec![];
+        };
+        let mut names = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let file_name = e.file_name().into_string().ok()?;
+                let name = file_name.strip_prefix("example_")?.strip_suffix(".txt")?;
+                Some(name.to_string())
+            })
             .collect::<Vec<String>>();
+        names.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+        names
+    }
+
+    fn from_file(day: u32, case: &Case) -> Result<Self, Error> {
+        Self::from_path(&Self::path(&Self::input_dir(), day, case))
+    }
+
+    fn from_input(input: &Input, day: u32, case: &Case) -> Result<Self, Error> {
+        match input {
+            Input::Dir(dir) => Self::from_path(&Self::path(dir, day, case)),
+            Input::File(path) => Self::from_path(path),
+            Input::Stdin => Self::from_reader(std::io::stdin().lock(), "<stdin>"),
+        }
+    }
+
+    fn from_path(path: &Path) -> Result<Self, Error> {
+        let filename = path.display().to_string();
+        let file = fs::File::open(path).map_err(|e| Error::from(e).with_source(Some(&filename)))?;
+        Self::from_reader(file, &filename)
+    }
+
+    fn from_reader(mut reader: impl Read, source: &str) -> Result<Self, Error> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|e| Error::from(e).with_source(Some(source)))?;
 
-        Self { data: content }
+        Ok(Self {
+            text,
+            source: Some(source.to_string()),
+        })
     }
 
-    fn from_string(data: String) -> Self {
-        Self { data: vec![data] }
+    fn from_string(text: String) -> Self {
+        Self { text, source: None }
     }
 
-    fn from_vec(data: Vec<String>) -> Self {
-        Self { data }
+    fn from_vec(lines: Vec<String>) -> Self {
+        Self::from_string(lines.join("\n"))
     }
 }
 
-#[derive(PartialEq, Eq, Debug)]
-struct Answer {
-    value: String,
+/// The answer to a part of a puzzle
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum Answer {
+    Int(i128),
+    Text(String),
+    /// Multiple lines of output, like a picture drawn on a grid
+    #[allow(dead_code)]
+    Grid(String),
+    /// The part is not solved yet
+    Unsolved,
+}
+
+impl Answer {
+    /// Joins the rows of a grid into a multi-line answer
+    #[allow(dead_code)]
+    fn grid<T: Display>(rows: impl IntoIterator<Item = T>) -> Self {
+        let rows = rows.into_iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        Self::Grid(rows.join("\n"))
+    }
 }
 
 impl Display for Answer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.value)
+        match self {
+            Answer::Int(x) => write!(f, "{x}"),
+            Answer::Text(x) | Answer::Grid(x) => write!(f, "{x}"),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
     }
 }
 
 impl From<i32> for Answer {
     fn from(value: i32) -> Self {
-        Self {
-            value: value.to_string(),
-        }
+        Self::Int(value.into())
     }
 }
 impl From<u32> for Answer {
     fn from(value: u32) -> Self {
-        Self {
-            value: value.to_string(),
-        }
+        Self::Int(value.into())
     }
 }
 impl From<u64> for Answer {
     fn from(value: u64) -> Self {
-        Self {
-            value: value.to_string(),
-        }
+        Self::Int(value.into())
     }
 }
 
 impl From<i64> for Answer {
     fn from(value: i64) -> Self {
-        Self {
-            value: value.to_string(),
-        }
+        Self::Int(value.into())
     }
 }
 
 impl From<usize> for Answer {
     fn from(value: usize) -> Self {
-        Self {
-            value: value.to_string(),
-        }
+        Self::Int(value as i128)
     }
 }
 
 impl From<&str> for Answer {
     fn from(value: &str) -> Self {
-        Self {
-            value: value.to_string(),
-        }
+        Self::Text(value.to_string())
     }
 }
 impl From<String> for Answer {
     fn from(value: String) -> Self {
-        Self { value }
+        Self::Text(value)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use day4::Day4;
-    use rstest::rstest;
-
     use super::*;
 
-    macro_rules! day {
-        ($Foo : ident, $Nr: expr) => {
-            &mut $Foo::new(ProblemData::from_file($Nr, Case::Real))
-        };
+    #[test]
+    fn answers_match_manifest() {
+        let dir = ProblemData::input_dir();
+        let answers = Answers::load(&dir).unwrap_or_else(|e| panic!("{e}"));
+        let mut checked = 0;
+        let mut failed = vec![];
+        for (day, part, case, expected) in answers.iter() {
+            // real inputs are not all checked in, skip the ones that are missing but not the ones
+            // that can't be read
+            let path = ProblemData::path(&dir, day, &case);
+            match fs::metadata(&path) {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                _ => {}
+            }
+            let data = ProblemData::from_path(&path).unwrap_or_else(|e| panic!("{e}"));
+            let entry = registry::get(day).expect("day is not registered");
+            let mut solver = (entry.new)(data).unwrap_or_else(|e| panic!("{e}"));
+            let answer = solver.solve(part).to_string();
+            if answer != expected {
+                failed.push(format!(
+                    "day {day} part {part} {case}: got {answer}, expected {expected}"
+                ));
+            }
+            checked += 1;
+        }
+        assert!(checked > 0);
+        assert!(failed.is_empty(), "{}", failed.join("\n"));
     }
-    macro_rules! day_test {
-        ($Foo : ident, $Nr: expr) => {
-            &mut $Foo::new(ProblemData::from_file($Nr, Case::Test))
-        };
+
+    #[test]
+    fn problem_data_views() {
+        let data = ProblemData::from_vec(vec!["ab".to_string(), "c".to_string()]);
+        assert_eq!(data.as_str(), "ab\nc");
+        assert_eq!(data.lines().collect::<Vec<_>>(), ["ab", "c"]);
+        assert_eq!(data.bytes()[1], b'b');
+        let mut line = String::new();
+        data.reader().read_line(&mut line).unwrap();
+        assert_eq!(line, "ab\n");
+    }
+
+    #[test]
+    fn answer_display() {
+        assert_eq!(Answer::from(-3).to_string(), "-3");
+        assert_eq!(Answer::from(u64::MAX).to_string(), "18446744073709551615");
+        assert_eq!(Answer::from("0,1,2").to_string(), "0,1,2");
+        assert_eq!(Answer::grid(["#.", ".#"]).to_string(), "#.\n.#");
+        assert_eq!(Answer::Unsolved.to_string(), "unsolved");
+        assert_ne!(Answer::from(0), Answer::Unsolved);
     }
 
-    #[rstest]
-    #[case(day!(Day1, 1), 3574690)]
-    #[case(day!(Day2, 2), 269)]
-    #[case(day!(Day3, 3), 187825547)]
-    #[case(day!(Day4, 4), 0)]
-    #[case(day!(Day5, 5), 4872)]
-    #[case(day!(Day6, 6), 5453)]
-    #[case(day!(Day7, 7), 1399219271639)]
-    #[case(day!(Day8, 8), 423)]
-    #[case(day!(Day11, 11), 193899)]
-    #[case(day!(Day17, 17), "2,3,4,7,5,7,3,0,7")]
-    fn solve_part1(#[case] d: &mut impl Solver, #[case] expected: i64) {
-        if expected > 0 {
-            let p1 = d.solve1();
-            assert_eq!(p1.into(), expected.into());
-        } else {
-            assert!(true);
-        }
-    }
-
-    #[rstest]
-    #[case(day!(Day1, 1), 22565391)]
-    #[case(day!(Day2, 2), 0)]
-    #[case(day!(Day3, 3), 85508223)]
-    #[case(day!(Day4, 4), 0)]
-    #[case(day!(Day5, 5), 5564)]
-    #[case(day!(Day6, 6), 0)]
-    // #[case(day!(Day7, 7), 0)]
-    #[case(day!(Day8, 8), 1287)]
-    fn solve_part2(#[case] d: &mut impl Solver, #[case] expected: u32) {
-        if expected > 0 {
-            let p1 = d.solve2();
-            assert_eq!(p1.into(), expected.into());
-        }
-    }
-
-    #[rstest]
-    #[case(day_test!(Day3, 3), 161)]
-    #[case(day_test!(Day4, 4), 18)]
-    #[case(day_test!(Day5, 5), 143)]
-    fn solve_part1_test_case(#[case] d: &mut impl Solver, #[case] expected: u32) {
-        if expected > 0 {
-            let p1 = d.solve1();
-            assert_eq!(p1.into(), expected.into());
-        }
-    }
-    #[rstest]
-    #[case(day_test!(Day2, 2), 0)]
-    fn solve_part2_test_case(#[case] d: &mut impl Solver, #[case] expected: u32) {
-        if expected > 0 {
-            let p1 = d.solve2();
-            assert_eq!(p1.into(), expected.into());
+    #[test]
+    fn case_from_str() {
+        assert_eq!("real".parse(), Ok(Case::Real));
+        assert_eq!("example:3".parse(), Ok(Case::Example("3".to_string())));
+        assert!("example:".parse::<Case>().is_err());
+        for name in ["../x", "a/b", "a\\b", ".."] {
+            assert_eq!(
+                format!("example:{name}").parse::<Case>(),
+                Err(format!("invalid example name '{name}'"))
+            );
         }
+        assert_eq!(Case::Example("3".to_string()).to_string(), "example:3");
     }
 }
@@ -0,0 +1,201 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    collections::BTreeMap,
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Once,
+    },
+    thread,
+    time::Duration,
+};
+
+/// The message a panic was started with, if it was a string
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|x| x.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+thread_local! {
+    /// Set while the thread runs a job whose panics are caught and reported by the caller
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Calls `f` and turns a panic into its message. The panic hook stays quiet for these panics, as
+/// the message is reported with the result, other panics are printed as usual.
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    static QUIET_HOOK: Once = Once::new();
+    QUIET_HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !CATCHING.get() {
+                hook(info);
+            }
+        }));
+    });
+
+    let outer = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(outer);
+    result.map_err(|e| panic_message(&*e))
+}
+
+/// Why an isolated job did not finish
+#[derive(Debug, PartialEq, Eq)]
+pub enum Failure {
+    Panic(String),
+    Timeout(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panic(message) => write!(f, "PANIC ({message})"),
+            Failure::Timeout(limit) => write!(f, "TIMEOUT (after {limit:?})"),
+        }
+    }
+}
+
+/// Calls `f` on `value`, catching panics without printing them, and gives the value back with the
+/// result so it can be used again. With a timeout `f` runs on its own thread, which is left
+/// running in the background if it doesn't finish in time, as threads can't be stopped from the
+/// outside.
+pub fn run_isolated<V: Send + 'static, T: Send + 'static>(
+    mut value: V,
+    timeout: Option<Duration>,
+    f: impl FnOnce(&mut V) -> T + Send + 'static,
+) -> Result<(V, T), Failure> {
+    let call = move || {
+        catch(|| f(&mut value))
+            .map(|result| (value, result))
+            .map_err(Failure::Panic)
+    };
+    let Some(timeout) = timeout else {
+        return call();
+    };
+
+    let (tx, rx) = mpsc::channel();
+    // the handle is dropped on purpose: after a timeout the thread keeps running detached until
+    // `f` returns or the process exits, there is no way to stop it and nothing to join
+    thread::spawn(move || {
+        // the receiver is gone after a timeout, there is nobody left to tell
+        let _ = tx.send(call());
+    });
+    rx.recv_timeout(timeout)
+        .unwrap_or(Err(Failure::Timeout(timeout)))
+}
+
+/// Calls `f` on every item on up to `jobs` threads and hands the results to `done` in the order
+/// of `items`, each as soon as it and every earlier one are finished. A panic in `f` is caught and
+/// handed on as its message instead of being printed, so one item can't take down the others.
+///
+/// With a single job everything runs on the calling thread.
+pub fn run_ordered<I: Sync, T: Send>(
+    items: &[I],
+    jobs: usize,
+    f: impl Fn(&I) -> T + Sync,
+    mut done: impl FnMut(&I, Result<T, String>),
+) {
+    let call = |item: &I| catch(|| f(item));
+    if jobs <= 1 {
+        for item in items {
+            done(item, call(item));
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|s| {
+        for _ in 0..jobs.min(items.len()) {
+            let (tx, next, call) = (tx.clone(), &next, &call);
+            s.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(i) else {
+                    break;
+                };
+                if tx.send((i, call(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut finished = BTreeMap::new();
+        let mut i = 0;
+        for (j, result) in rx {
+            finished.insert(j, result);
+            while let Some(result) = finished.remove(&i) {
+                done(&items[i], result);
+                i += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn results_are_in_order() {
+        let items = (0..20).collect::<Vec<u64>>();
+        let mut results = vec![];
+        run_ordered(
+            &items,
+            4,
+            |&x| {
+                // later items finish first
+                thread::sleep(Duration::from_millis(20 - x));
+                x * 2
+            },
+            |&x, r| results.push((x, r)),
+        );
+        let expected = items.iter().map(|&x| (x, Ok(x * 2))).collect::<Vec<_>>();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn isolated_timeout_and_panic() {
+        let limit = Duration::from_millis(50);
+        assert_eq!(run_isolated(2, Some(limit * 100), |x| *x * 3), Ok((2, 6)));
+        assert_eq!(run_isolated(2, None, |x| *x * 3), Ok((2, 6)));
+
+        // blocks until the sender is dropped, which only happens after the timeout
+        let (tx, rx) = mpsc::channel::<()>();
+        let failure = run_isolated(rx, Some(limit), |rx| rx.recv()).unwrap_err();
+        assert_eq!(failure, Failure::Timeout(limit));
+        assert_eq!(failure.to_string(), "TIMEOUT (after 50ms)");
+        drop(tx);
+
+        let failure = run_isolated((), Some(limit * 100), |_| panic!("bad {}", 1)).unwrap_err();
+        assert_eq!(failure.to_string(), "PANIC (bad 1)");
+        assert_eq!(
+            run_isolated((), None, |_| panic!("bad {}", 1)),
+            Err(Failure::Panic("bad 1".to_string()))
+        );
+    }
+
+    #[test]
+    fn panics_are_caught() {
+        for jobs in [1, 3] {
+            let mut results = vec![];
+            run_ordered(
+                &[1, 0, 2],
+                jobs,
+                |&x| {
+                    assert!(x != 0, "zero");
+                    x
+                },
+                |_, r| results.push(r),
+            );
+            assert_eq!(results, [Ok(1), Err("zero".to_string()), Ok(2)]);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{error::Error, DynSolver, ProblemData, Solver};
+
+/// An implemented day and how to create its solver
+pub struct DayEntry {
+    pub day: u32,
+    pub title: &'static str,
+    pub new: fn(ProblemData) -> Result<Box<dyn DynSolver>, Error>,
+}
+
+fn boxed<S: Solver + 'static>(data: ProblemData) -> Result<Box<dyn DynSolver>, Error> {
+    let source = data.source.clone();
+    match S::try_new(data) {
+        Ok(s) => Ok(Box::new(s)),
+        Err(e) => Err(e.with_source(source.as_deref())),
+    }
+}
+
+macro_rules! days {
+    ($($Nr: literal => $module: ident :: $Day: ident, $Title: literal;)*) => {
+        /// Every implemented day, ordered by day number
+        pub const DAYS: &[DayEntry] = &[
+            $(DayEntry {
+                day: $Nr,
+                title: $Title,
+                new: boxed::<crate::$module::$Day>,
+            },)*
+        ];
+    };
+}
+
+days! {
+    1 => day1::Day1, "Historian Hysteria";
+    2 => day2::Day2, "Red-Nosed Reports";
+    3 => day3::Day3, "Mull It Over";
+    4 => day4::Day4, "Ceres Search";
+    5 => day5::Day5, "Print Queue";
+    6 => day6::Day6, "Guard Gallivant";
+    7 => day7::Day7, "Bridge Repair";
+    8 => day8::Day8, "Resonant Collinearity";
+    11 => day11::Day11, "Plutonian Pebbles";
+    17 => day17::Day17, "Chronospatial Computer";
+}
+
+pub fn get(day: u32) -> Option<&'static DayEntry> {
+    DAYS.iter().find(|x| x.day == day)
+}
+
+pub fn all() -> impl Iterator<Item = &'static DayEntry> {
+    DAYS.iter()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_ordered_and_unique() {
+        assert!(DAYS.windows(2).all(|w| w[0].day < w[1].day));
+    }
+
+    #[test]
+    fn get_day() {
+        assert_eq!(get(17).map(|x| x.title), Some("Chronospatial Computer"));
+        assert!(get(9).is_none());
+    }
+}
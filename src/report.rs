@@ -0,0 +1,362 @@
+use std::{
+    fmt::Write,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    answers::{Answers, Status},
+    pool,
+    registry::DayEntry,
+    Answer, Case, Input, Part, ProblemData,
+};
+
+/// One solved part of a day
+#[derive(Debug)]
+pub struct Row {
+    pub day: u32,
+    pub part: Part,
+    /// The answer, or why the day could not be read, parsed or solved
+    pub answer: Result<Answer, String>,
+    pub expected: Option<String>,
+    /// `None` when there is no answer
+    pub status: Option<Status>,
+    /// Time spent solving the part, parsing excluded
+    pub time: Duration,
+}
+
+impl Row {
+    pub fn status_name(&self) -> &'static str {
+        match self.status {
+            Some(Status::Pass) => "PASS",
+            Some(Status::Fail(_)) => "FAIL",
+            Some(Status::Unknown) => "UNKNOWN",
+            Some(Status::Unsolved) => "UNSOLVED",
+            None => "ERROR",
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        !matches!(self.status, Some(Status::Fail(_)) | None)
+    }
+
+    /// The answer on a single line, grid rows are separated by `\n` like in the manifest
+    fn answer_text(&self) -> String {
+        match &self.answer {
+            Ok(answer) => answer.to_string().replace('\n', "\\n"),
+            Err(e) => e.clone(),
+        }
+    }
+}
+
+/// Solves `parts` of every entry from the `case` inputs in `dir` and checks them against the
+/// manifest there. Every part is isolated like in `run`, one that panics or takes longer than
+/// `timeout` is reported as an error and the next part gets a fresh solver.
+pub fn collect<'a>(
+    entries: impl Iterator<Item = &'a DayEntry>,
+    dir: &Path,
+    case: &Case,
+    parts: &[Part],
+    timeout: Option<Duration>,
+) -> Result<Vec<Row>, crate::error::Error> {
+    let answers = Answers::load(dir)?;
+    let input = Input::Dir(dir.to_path_buf());
+    let mut rows = vec![];
+    for entry in entries {
+        let expected = |part| {
+            answers
+                .expected(entry.day, part, case)
+                .map(|x| x.to_string())
+        };
+        let error = |part, e: String| Row {
+            day: entry.day,
+            part,
+            answer: Err(e),
+            expected: expected(part),
+            status: None,
+            time: Duration::ZERO,
+        };
+        let data = match ProblemData::from_input(&input, entry.day, case) {
+            Ok(data) => data,
+            Err(e) => {
+                rows.extend(parts.iter().map(|&part| error(part, e.to_string())));
+                continue;
+            }
+        };
+
+        let mut solver = None;
+        for &part in parts {
+            let current = match solver.take() {
+                Some(s) => s,
+                None => match (entry.new)(data.clone()) {
+                    Ok(s) => s,
+                    Err(e) => {
+                        rows.push(error(part, e.to_string()));
+                        continue;
+                    }
+                },
+            };
+            let start = Instant::now();
+            let result = pool::run_isolated(current, timeout, move |s| s.solve(part));
+            let time = start.elapsed();
+            let (answer, status) = match result {
+                Ok((s, answer)) => {
+                    solver = Some(s);
+                    let status = answers.check(entry.day, part, case, &answer);
+                    (Ok(answer), Some(status))
+                }
+                Err(failure) => (Err(failure.to_string()), None),
+            };
+            rows.push(Row {
+                day: entry.day,
+                part,
+                answer,
+                expected: expected(part),
+                status,
+                time,
+            });
+        }
+    }
+    Ok(rows)
+}
+
+/// Aligned table with one row per part and a count of every status at the bottom
+pub fn table(rows: &[Row]) -> String {
+    let header = ["day", "part", "answer", "expected", "status", "time"];
+    let cells = rows
+        .iter()
+        .map(|row| {
+            [
+                row.day.to_string(),
+                row.part.to_string(),
+                row.answer_text(),
+                row.expected
+                    .clone()
+                    .unwrap_or_default()
+                    .replace('\n', "\\n"),
+                row.status_name().to_string(),
+                format!("{:.2?}", row.time),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let mut widths = header.map(|x| x.len());
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let header = header.map(|x| x.to_string());
+    for row in std::iter::once(&header).chain(&cells) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(i, (cell, width))| match i {
+                // numbers are right aligned
+                0 | 1 | 5 => format!("{cell:>width$}"),
+                _ => format!("{cell:<width$}"),
+            })
+            .collect::<Vec<_>>()
+            .join("  ");
+        writeln!(out, "{}", line.trim_end()).unwrap();
+    }
+
+    let count = |name| rows.iter().filter(|x| x.status_name() == name).count();
+    write!(
+        out,
+        "\n{} passed, {} failed, {} unknown, {} unsolved, {} errors",
+        count("PASS"),
+        count("FAIL"),
+        count("UNKNOWN"),
+        count("UNSOLVED"),
+        count("ERROR")
+    )
+    .unwrap();
+    out
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Array of objects, one per row, with `null` for an unknown expected answer and `error` set
+/// instead of `answer` when the day could not be solved
+pub fn json(rows: &[Row]) -> String {
+    let optional = |x: Option<&str>| x.map(json_string).unwrap_or_else(|| "null".to_string());
+    let objects = rows
+        .iter()
+        .map(|row| {
+            let (answer, error) = match &row.answer {
+                Ok(answer) => (Some(answer.to_string()), None),
+                Err(e) => (None, Some(e.as_str())),
+            };
+            format!(
+                "  {{\"day\": {}, \"part\": {}, \"answer\": {}, \"expected\": {}, \"status\": \"{}\", \"time_ns\": {}, \"error\": {}}}",
+                row.day,
+                row.part,
+                optional(answer.as_deref()),
+                optional(row.expected.as_deref()),
+                row.status_name(),
+                row.time.as_nanos(),
+                optional(error),
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[\n{}\n]", objects.join(",\n"))
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Comma separated values with a header line, errors are put in the answer column
+pub fn csv(rows: &[Row]) -> String {
+    let mut out = String::from("day,part,answer,expected,status,time_ns\n");
+    for row in rows {
+        let answer = match &row.answer {
+            Ok(answer) => answer.to_string(),
+            Err(e) => e.clone(),
+        };
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            row.day,
+            row.part,
+            csv_field(&answer),
+            csv_field(row.expected.as_deref().unwrap_or_default()),
+            row.status_name(),
+            row.time.as_nanos()
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{error::Error, Solver};
+
+    /// Panics in part 1 and never finishes part 2
+    struct Stuck;
+
+    // the answers after the endless parts only give the functions a return type
+    #[allow(unreachable_code)]
+    impl Solver for Stuck {
+        fn try_new(_: ProblemData) -> Result<Self, Error> {
+            Ok(Self)
+        }
+
+        fn solve1(&mut self) -> impl Into<Answer> {
+            panic!("part 1 is broken");
+            Answer::Unsolved
+        }
+
+        fn solve2(&mut self) -> impl Into<Answer> {
+            loop {
+                std::thread::park();
+            }
+            Answer::Unsolved
+        }
+    }
+
+    #[test]
+    fn failing_parts_are_rows() {
+        let dir = std::env::temp_dir().join(format!("aoc_report_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("day1.txt"), "").unwrap();
+        let entry = DayEntry {
+            day: 1,
+            title: "Stuck",
+            new: |data| Ok(Box::new(Stuck::try_new(data)?)),
+        };
+        let timeout = Some(Duration::from_millis(50));
+        let parts = [Part::One, Part::Two];
+        let rows = collect([&entry].into_iter(), &dir, &Case::Real, &parts, timeout).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let answers = rows.iter().map(|x| x.answer.clone()).collect::<Vec<_>>();
+        assert_eq!(
+            answers,
+            [
+                Err("PANIC (part 1 is broken)".to_string()),
+                Err("TIMEOUT (after 50ms)".to_string())
+            ]
+        );
+        assert!(rows.iter().all(|x| x.status_name() == "ERROR"));
+    }
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                day: 17,
+                part: Part::One,
+                answer: Ok(Answer::from("0,1,2")),
+                expected: Some("0,1,2".to_string()),
+                status: Some(Status::Pass),
+                time: Duration::from_micros(5),
+            },
+            Row {
+                day: 25,
+                part: Part::Two,
+                answer: Err("data/day25.txt: \"missing\"".to_string()),
+                expected: None,
+                status: None,
+                time: Duration::ZERO,
+            },
+        ]
+    }
+
+    #[test]
+    fn table_counts_statuses() {
+        let table = table(&rows());
+        let lines = table.lines().collect::<Vec<_>>();
+        assert!(lines[0].starts_with("day  part  answer"));
+        assert!(lines[1].starts_with(" 17     1  0,1,2"));
+        assert!(lines[2].contains("ERROR"));
+        assert_eq!(
+            lines.last(),
+            Some(&"1 passed, 0 failed, 0 unknown, 0 unsolved, 1 errors")
+        );
+    }
+
+    #[test]
+    fn json_export() {
+        let json = json(&rows());
+        assert!(json.contains(
+            r#"{"day": 17, "part": 1, "answer": "0,1,2", "expected": "0,1,2", "status": "PASS", "time_ns": 5000, "error": null}"#
+        ));
+        assert!(json.contains(r#""answer": null, "expected": null, "status": "ERROR""#));
+        assert!(json.contains(r#""error": "data/day25.txt: \"missing\"""#));
+    }
+
+    #[test]
+    fn csv_export() {
+        let csv = csv(&rows());
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], "day,part,answer,expected,status,time_ns");
+        assert_eq!(lines[1], "17,1,\"0,1,2\",\"0,1,2\",PASS,5000");
+        assert_eq!(
+            lines[2],
+            "25,2,\"data/day25.txt: \"\"missing\"\"\",,ERROR,0"
+        );
+    }
+}
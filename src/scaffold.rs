@@ -0,0 +1,203 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::Error, Case, ProblemData};
+
+/// Source of a freshly generated day, both parts unsolved
+fn module_source(day: u32) -> String {
+    format!(
+        "\
+use crate::{{error::Error, Answer, ProblemData, Solver}};
+
+#[allow(dead_code)]
+pub struct Day{day} {{
+    data: ProblemData,
+}}
+
+impl Solver for Day{day} {{
+    fn try_new(data: ProblemData) -> Result<Self, Error> {{
+        Ok(Self {{ data }})
+    }}
+
+    fn solve1(&mut self) -> impl Into<Answer> {{
+        Answer::Unsolved
+    }}
+
+    fn solve2(&mut self) -> impl Into<Answer> {{
+        Answer::Unsolved
+    }}
+}}
+"
+    )
+}
+
+fn join_lines(lines: Vec<String>) -> String {
+    lines.into_iter().map(|x| x + "\n").collect()
+}
+
+/// Adds `mod dayN;` to the block of module declarations in `main.rs`, keeping it sorted
+fn add_mod(main: &str, day: u32) -> Result<String, Error> {
+    let mut lines = main.lines().map(|x| x.to_string()).collect::<Vec<_>>();
+    let is_mod = |x: &String| x.starts_with("mod ") && x.ends_with(';');
+    let start = lines
+        .iter()
+        .position(is_mod)
+        .ok_or_else(|| Error::new("no module declarations found in main.rs"))?;
+    let end = start + lines[start..].iter().take_while(|x| is_mod(x)).count();
+
+    let new = format!("mod day{day};");
+    if lines[start..end].contains(&new) {
+        return Err(Error::new(format!(
+            "day{day} is already declared in main.rs"
+        )));
+    }
+    let mut block = lines.drain(start..end).collect::<Vec<_>>();
+    block.push(new);
+    block.sort_by(|a, b| a.trim_end_matches(';').cmp(b.trim_end_matches(';')));
+    lines.splice(start..start, block);
+    Ok(join_lines(lines))
+}
+
+/// Adds the day to the `days!` list in `registry.rs`, keeping it ordered by day number
+fn add_entry(registry: &str, day: u32, title: &str) -> Result<String, Error> {
+    let mut lines = registry.lines().map(|x| x.to_string()).collect::<Vec<_>>();
+    let start = lines
+        .iter()
+        .position(|x| x == "days! {")
+        .ok_or_else(|| Error::new("no 'days!' list found in registry.rs"))?
+        + 1;
+
+    let mut at = start;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let Some((nr, _)) = line.trim().split_once(" =>") else {
+            break;
+        };
+        let nr = nr
+            .parse::<u32>()
+            .map_err(|_| Error::at_line(i, format!("invalid day '{nr}'")))?;
+        if nr == day {
+            return Err(Error::new(format!("day {day} is already registered")));
+        }
+        if nr < day {
+            at = i + 1;
+        }
+    }
+    lines.insert(at, format!("    {day} => day{day}::Day{day}, \"{title}\";"));
+    Ok(join_lines(lines))
+}
+
+/// Generates the solver module of a new day in `root/src`, registers it and creates empty
+/// real, test and example inputs in `data_dir`. Returns every file that was written, existing
+/// inputs are left alone
+pub fn new_day(root: &Path, data_dir: &Path, day: u32, title: &str) -> Result<Vec<PathBuf>, Error> {
+    if !(1..=25).contains(&day) {
+        return Err(Error::new(format!("invalid day {day}, expected 1 to 25")));
+    }
+    if title.contains(['"', '\\']) {
+        return Err(Error::new(
+            "the title can not contain quotes or backslashes",
+        ));
+    }
+
+    let src = root.join("src");
+    let registry_path = src.join("registry.rs");
+    if !registry_path.is_file() {
+        return Err(Error::new(format!(
+            "{} not found, {} is not the root of the crate",
+            registry_path.display(),
+            root.display()
+        )));
+    }
+    let module = src.join(format!("day{day}.rs"));
+    if module.exists() {
+        return Err(Error::new(format!("{} already exists", module.display())));
+    }
+    let read = |path: &Path| {
+        fs::read_to_string(path)
+            .map_err(|e| Error::from(e).with_source(Some(&path.display().to_string())))
+    };
+    let main_path = src.join("main.rs");
+    let main = add_mod(&read(&main_path)?, day)?;
+    let registry = add_entry(&read(&registry_path)?, day, title)?;
+
+    let mut written = vec![];
+    let mut write = |path: PathBuf, content: &str| {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, content)?;
+        written.push(path);
+        Ok::<_, std::io::Error>(())
+    };
+    write(module, &module_source(day))?;
+    write(main_path, &main)?;
+    write(registry_path, &registry)?;
+    for case in [Case::Real, Case::Test, Case::Example("1".to_string())] {
+        let path = ProblemData::path(data_dir, day, &case);
+        if !path.exists() {
+            write(path, "")?;
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mod_is_inserted_sorted() {
+        let main = "use x;\n\nmod answers;\nmod day1;\nmod day2;\nmod grid;\nfn main() {}\n";
+        assert_eq!(
+            add_mod(main, 12).unwrap(),
+            "use x;\n\nmod answers;\nmod day1;\nmod day12;\nmod day2;\nmod grid;\nfn main() {}\n"
+        );
+        assert!(add_mod(main, 2).is_err());
+    }
+
+    #[test]
+    fn entry_is_inserted_in_day_order() {
+        let registry = "days! {\n    1 => day1::Day1, \"A\";\n    11 => day11::Day11, \"B\";\n}\n";
+        assert_eq!(
+            add_entry(registry, 9, "New").unwrap(),
+            "days! {\n    1 => day1::Day1, \"A\";\n    9 => day9::Day9, \"New\";\n    11 => day11::Day11, \"B\";\n}\n"
+        );
+        assert!(add_entry(registry, 25, "")
+            .unwrap()
+            .contains("\"B\";\n    25 =>"));
+        assert!(add_entry(registry, 11, "").is_err());
+    }
+
+    #[test]
+    fn new_day_writes_files() {
+        let root = std::env::temp_dir().join(format!("aoc_scaffold_{}", std::process::id()));
+        let src = root.join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("main.rs"), "mod day1;\n").unwrap();
+        fs::write(
+            src.join("registry.rs"),
+            "days! {\n    1 => day1::Day1, \"A\";\n}\n",
+        )
+        .unwrap();
+
+        let data = root.join("data");
+        let written = new_day(&root, &data, 3, "Mull It Over").unwrap();
+        let exists = |x: &str| root.join(x).exists();
+        assert_eq!(written.len(), 6);
+        assert!(exists("src/day3.rs") && exists("data/day3.txt") && exists("data/day3_test.txt"));
+        assert!(exists("data/day3/example_1.txt"));
+        assert!(new_day(&root, &data, 3, "Again").is_err());
+        assert_eq!(
+            new_day(&data, &data, 4, "").unwrap_err().message,
+            format!(
+                "{} not found, {} is not the root of the crate",
+                data.join("src/registry.rs").display(),
+                data.display()
+            )
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,4 +1,133 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+fn aoc(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_aoc_2024"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(args)
+        .output()
+        .expect("failed to run binary")
+}
+
 #[test]
 fn day2_part1() {
-    assert!(true)
+    let out = aoc(&["run", "--day", "2", "--part", "1"]);
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "part1: 269  PASS\n");
+}
+
+#[test]
+fn day_not_implemented() {
+    let out = aoc(&["run", "--day", "25"]);
+    assert!(!out.status.success());
+    assert!(String::from_utf8_lossy(&out.stderr).contains("day 25 is not implemented"));
+}
+
+#[test]
+fn examples_of_every_day() {
+    let out = aoc(&["run", "--all", "--examples", "--part", "1"]);
+    assert!(out.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "day 17: Chronospatial Computer\n  example 2:\n    part1: 0,1,2  PASS\n  example 3:\n    part1: 4,2,5,6,7,7,7,7,3,1,0  PASS\n"
+    );
+
+    // asking for a single day without examples is still an error
+    assert!(!aoc(&["run", "-d", "1", "--examples"]).status.success());
+}
+
+#[test]
+fn invalid_arguments() {
+    let out = aoc(&["run", "--part", "3"]);
+    assert_eq!(out.status.code(), Some(2));
+}
+
+#[test]
+fn input_dir_from_flag_and_env() {
+    let data = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    let expected = "part1: 161  PASS\n";
+
+    let out = Command::new(env!("CARGO_BIN_EXE_aoc_2024"))
+        .current_dir(std::env::temp_dir())
+        .args([
+            "run",
+            "-d",
+            "3",
+            "-p",
+            "1",
+            "-c",
+            "test",
+            "--input-dir",
+            data,
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout), expected);
+
+    let out = Command::new(env!("CARGO_BIN_EXE_aoc_2024"))
+        .current_dir(std::env::temp_dir())
+        .env("AOC_INPUT_DIR", data)
+        .args(["run", "-d", "3", "-p", "1", "-c", "test"])
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout), expected);
+}
+
+#[test]
+fn input_from_stdin() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc_2024"))
+        .args(["run", "-d", "3", "-p", "1", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)")
+        .unwrap();
+    let out = child.wait_with_output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "part1: 33\n");
+}
+
+#[test]
+fn cached_answers_follow_the_input() {
+    let dir = std::env::temp_dir().join(format!("aoc_cached_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let dir_arg = dir.to_str().unwrap();
+    let run = |input: &str| {
+        std::fs::write(dir.join("day3.txt"), input).unwrap();
+        let out = aoc(&[
+            "run",
+            "-d",
+            "3",
+            "-p",
+            "1",
+            "--cached",
+            "--input-dir",
+            dir_arg,
+        ]);
+        String::from_utf8_lossy(&out.stdout).into_owned()
+    };
+
+    std::fs::write(dir.join("day3.txt"), "mul(2,4)").unwrap();
+    assert!(aoc(&["run", "-d", "3", "--input-dir", dir_arg])
+        .status
+        .success());
+    assert!(!dir.join(".answer_cache.txt").exists());
+
+    assert_eq!(run("mul(2,4)"), "part1: 8  UNKNOWN\n");
+    assert_eq!(run("mul(2,4)"), "part1: 8  UNKNOWN  (cached)\n");
+    assert_eq!(run("mul(3,4)"), "part1: 12  UNKNOWN\n");
+
+    // part 2 isn't cached yet, so part 1 is solved again along with it
+    let out = aoc(&["run", "-d", "3", "--cached", "--input-dir", dir_arg]);
+    assert_eq!(
+        String::from_utf8_lossy(&out.stdout),
+        "part1: 12  UNKNOWN\npart2: 12  UNKNOWN\n"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
 }